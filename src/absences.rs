@@ -1,7 +1,79 @@
 //! Absences
 
-use crate::{time::MyDate, user::User, utils};
-use ekreta::{Absence, Res};
+use crate::{
+    config::Config,
+    school_year::{self, SchoolDay},
+    time::{MyDate, pretty_day},
+    timetable,
+    user::User,
+    utils,
+};
+use chrono::{Datelike, Local, NaiveDate, Weekday};
+use ekreta::{Absence, LDateTime, Lesson, Res};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use yansi::Paint;
+
+/// a subject can't be rated if more of its lessons were missed
+const SUBJECT_LIMIT: f32 = 0.3;
+/// at most this many lessons can be missed in a school year
+const YEAR_LIMIT: usize = 250;
+/// this many minutes of lateness make up an unjustified lesson
+const LATE_MINUTES_PER_LESSON: u32 = 45;
+/// warn if the next unjustified lesson is this close, in minutes
+const LATE_WARN_MINUTES: u32 = 15;
+/// months of the calendar shown next to each other
+const CALENDAR_COLUMNS: usize = 3;
+/// width of a day in the calendar
+const CALENDAR_CELL: usize = 3;
+/// [`Absence::igazolas_allapota`] of absences still to be justified
+const TO_JUSTIFY: &str = "Igazolando";
+
+/// how close absences are to a limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum WarningLevel {
+    Fine,
+    /// past half of the limit
+    Watch,
+    /// past 80% of the limit
+    Danger,
+    /// the limit is reached
+    Over,
+}
+impl WarningLevel {
+    /// level of `part` of `limit`
+    fn of(part: f32, limit: f32) -> Self {
+        match part / limit {
+            r if r >= 1. => Self::Over,
+            r if r >= 0.8 => Self::Danger,
+            r if r >= 0.5 => Self::Watch,
+            _ => Self::Fine,
+        }
+    }
+    fn disp(self) -> String {
+        match self {
+            Self::Fine => "rendben".to_string(),
+            Self::Watch => "figyelj".yellow().to_string(),
+            Self::Danger => "veszélyes".red().to_string(),
+            Self::Over => "túllépve".red().bold().to_string(),
+        }
+    }
+}
+
+/// absences of a subject compared to the lessons held
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubjectAbsences {
+    pub subject: String,
+    pub justified: usize,
+    /// not justified yet, or won't be
+    pub unjustified: usize,
+    /// lessons held so far in this school year
+    pub held: usize,
+    /// share of lessons missed, in percent
+    pub percent: Option<f32>,
+    /// compared to the 30% limit
+    pub level: WarningLevel,
+}
 
 pub fn handle(user: &User, subj: Option<String>, count: bool, args: &crate::Args) -> Res<()> {
     let mut absences = user.get_absences((None, None))?;
@@ -24,6 +96,612 @@ pub fn handle(user: &User, subj: Option<String>, count: bool, args: &crate::Args
     utils::print_table(&absences, headers, args.reverse, args.number, disp)
 }
 
+/// lessons of a subject still to be held this school year, and how many of them can be missed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Projection {
+    pub subject: String,
+    /// missed lessons so far, justified or not
+    pub missed: usize,
+    /// lessons held so far in this school year
+    pub held: usize,
+    /// lessons projected from the weekly timetable until the end of the school year
+    pub remaining: usize,
+    /// lessons that can still be missed before reaching the 30% limit
+    pub allowed: usize,
+    /// missed lessons compared to the 30% of the whole year
+    pub level: WarningLevel,
+}
+
+/// lateness summed up for a month or a subject
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Lateness {
+    /// the month or the subject
+    pub of: String,
+    /// number of times late
+    pub count: usize,
+    pub minutes: u32,
+    /// minutes not justified (yet), only these make up unjustified lessons
+    pub unjustified: u32,
+}
+
+/// missed lessons of a day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Incident {
+    pub date: NaiveDate,
+    /// number of the first and the last lesson missed
+    pub lessons: (i64, i64),
+    /// number of lessons missed
+    pub count: usize,
+    /// number of lessons by [`Absence::igazolas_allapota`]
+    pub status: BTreeMap<String, usize>,
+    /// a note should be handed in until this day, if there's anything to justify
+    pub deadline: Option<NaiveDate>,
+}
+
+/// missed lessons grouped by day, with the deadline of justifying them
+pub fn handle_grouped(
+    user: &User,
+    conf: &Config,
+    subj: Option<String>,
+    args: &crate::Args,
+) -> Res<()> {
+    let mut absences = user.get_absences((None, None))?;
+    absences.retain(|abs| !is_lateness(abs));
+    if let Some(subject) = subj {
+        filter_by_subject(&mut absences, &subject);
+    }
+    let cal = user.get_school_year().unwrap_or_default();
+    let deadline = |day| school_year::nth_teaching_day_after(&cal, day, conf.justify_days);
+    let incidents = incidents(&absences, deadline);
+
+    let headers = ["nap", "órák", "ennyi", "igazolás", "határidő"].into_iter();
+    let disp = if args.machine {
+        None
+    } else {
+        Some(display_incident)
+    };
+    utils::print_table(&incidents, headers, args.reverse, args.number, disp)
+}
+
+/// [`Incident`]s of `absences`, oldest first, `deadline` is calculated from the day of it
+pub fn incidents(absences: &[Absence], deadline: impl Fn(NaiveDate) -> NaiveDate) -> Vec<Incident> {
+    let mut incidents: Vec<Incident> = vec![];
+    let mut absences = absences.iter().collect::<Vec<_>>();
+    absences.sort_by_key(|abs| abs.ora.kezdo_datum);
+    for abs in absences {
+        let date = abs.ora.kezdo_datum.date_naive();
+        let num = abs.ora.oraszam;
+        if let Some(incident) = incidents.last_mut()
+            && incident.date == date
+        {
+            incident.lessons.0 = incident.lessons.0.min(num);
+            incident.lessons.1 = incident.lessons.1.max(num);
+            incident.count += 1;
+            *incident
+                .status
+                .entry(abs.igazolas_allapota.clone())
+                .or_default() += 1;
+        } else {
+            incidents.push(Incident {
+                date,
+                lessons: (num, num),
+                count: 1,
+                status: BTreeMap::from([(abs.igazolas_allapota.clone(), 1)]),
+                deadline: None,
+            });
+        }
+    }
+    for incident in &mut incidents {
+        let to_justify = incident.status.contains_key(TO_JUSTIFY);
+        incident.deadline = to_justify.then(|| deadline(incident.date));
+    }
+    incidents
+}
+
+/// months of the school year as a calendar, days colored by the number of lessons missed
+pub fn handle_calendar(
+    user: &User,
+    conf: &Config,
+    subj: Option<String>,
+    args: &crate::Args,
+) -> Res<()> {
+    let mut absences = user.get_absences((None, None))?;
+    if let Some(subject) = subj {
+        filter_by_subject(&mut absences, &subject);
+    }
+    let (late, mut missed): (Vec<_>, Vec<_>) = absences.into_iter().partition(is_lateness);
+    missed.sort_by_key(|abs| abs.ora.kezdo_datum);
+    let mut late = late
+        .iter()
+        .map(|abs| abs.ora.kezdo_datum.date_naive())
+        .collect::<Vec<_>>();
+    late.dedup();
+    let cal = user.get_school_year().unwrap_or_default();
+    let deadline = |day| school_year::nth_teaching_day_after(&cal, day, conf.justify_days);
+    let incidents = incidents(&missed, deadline);
+
+    if args.machine {
+        let data = serde_json::json!({ "missed": incidents, "late": late });
+        println!("{data}");
+        return Ok(());
+    }
+    let today = Local::now().date_naive();
+    let start = school_year::year_start_of(&cal, today);
+    let end = school_year::year_end_of(&cal, today);
+    let day_cell = |day: NaiveDate| {
+        if day < start || day > end {
+            return " ".repeat(CALENDAR_CELL);
+        }
+        let pad = " ".repeat(CALENDAR_CELL - 1);
+        if let Some(incident) = incidents.iter().find(|inc| inc.date == day) {
+            let glyph = if incident.status.keys().all(|status| status == "Igazolt") {
+                "●"
+            } else {
+                "✕"
+            };
+            return match incident.count {
+                1..=2 => format!("{}{pad}", glyph.yellow()),
+                3..=5 => format!("{}{pad}", glyph.red()),
+                _ => format!("{}{pad}", glyph.red().bold()),
+            };
+        }
+        if late.contains(&day) {
+            format!("{}{pad}", "◦".magenta())
+        } else if school_year::is_teaching_day_or_weekday(&cal, day) {
+            format!("·{pad}")
+        } else {
+            format!("{}{pad}", "·".dim())
+        }
+    };
+
+    let months = start
+        .iter_days()
+        .take_while(|day| *day <= end)
+        .filter(|day| day.day() == 1 || *day == start)
+        .map(|first| month_grid(first, day_cell))
+        .collect::<Vec<_>>();
+    let width = CALENDAR_CELL * 7;
+    for row_of_months in months.chunks(CALENDAR_COLUMNS) {
+        let height = row_of_months.iter().map(Vec::len).max().unwrap_or_default();
+        for i in 0..height {
+            let lines = row_of_months.iter().map(|month| {
+                let empty = " ".repeat(width);
+                month.get(i).cloned().unwrap_or(empty)
+            });
+            println!("{}", lines.collect::<Vec<_>>().join("  "));
+        }
+        println!();
+    }
+    println!(
+        "{} igazolt  {} igazolatlan vagy igazolandó  {} csak késés  · nincs hiányzás  {} szünet",
+        "●".yellow(),
+        "✕".yellow(),
+        "◦".magenta(),
+        "·".dim()
+    );
+    println!(
+        "hiányzott órák: {} 1-2  {} 3-5  {} 6+",
+        "●".yellow(),
+        "●".red(),
+        "●".red().bold()
+    );
+    Ok(())
+}
+
+/// lines of the calendar of the month of `first`, each day shown by `day_cell`
+fn month_grid(first: NaiveDate, day_cell: impl Fn(NaiveDate) -> String) -> Vec<String> {
+    let width = CALENDAR_CELL * 7;
+    let month = crate::time::local_midnight(first).map_or("", |dt| dt.hun_month());
+    let title = format!("{:<width$}", format!("{}. {month}", first.year()));
+    let mut lines = vec![title.bold().to_string()];
+    let days = ["h", "k", "sz", "cs", "p", "sz", "v"].map(|d| format!("{d:<CALENDAR_CELL$}"));
+    lines.push(days.concat().dim().to_string());
+
+    let first_of_month = first.with_day(1).unwrap_or(first);
+    let mut line =
+        " ".repeat(CALENDAR_CELL * first_of_month.weekday().num_days_from_monday() as usize);
+    let days_of_month = first_of_month
+        .iter_days()
+        .take_while(|d| d.month() == first.month());
+    for day in days_of_month {
+        line.push_str(&day_cell(day));
+        if day.weekday() == Weekday::Sun {
+            lines.push(std::mem::take(&mut line));
+        } else if day
+            .succ_opt()
+            .is_none_or(|next| next.month() != first.month())
+        {
+            // pad the last week, so that the next month can be shown next to it
+            let left = 6 - day.weekday().num_days_from_monday() as usize;
+            line.push_str(&" ".repeat(CALENDAR_CELL * left));
+            lines.push(std::mem::take(&mut line));
+        }
+    }
+    lines
+}
+
+fn display_incident(incident: &Incident) -> Vec<String> {
+    let date = pretty_day(incident.date);
+    let (first, last) = incident.lessons;
+    let lessons = if first == last {
+        format!("{first}.")
+    } else {
+        format!("{first}-{last}.")
+    };
+    let count = incident.count.to_string();
+    let disp_status = |status: &str| status.to_lowercase().replace("do", "dó");
+    let status = if let [(status, _)] = incident.status.iter().collect::<Vec<_>>()[..] {
+        disp_status(status)
+    } else {
+        let statuses = incident.status.iter();
+        let statuses = statuses.map(|(status, n)| format!("{n} {}", disp_status(status)));
+        statuses.collect::<Vec<_>>().join(", ")
+    };
+    let today = Local::now().date_naive();
+    let deadline = match incident.deadline {
+        Some(deadline) if deadline < today => pretty_day(deadline).red().to_string(),
+        Some(deadline) if deadline <= today.succ_opt().unwrap_or(today) => {
+            pretty_day(deadline).yellow().to_string()
+        }
+        Some(deadline) => pretty_day(deadline),
+        None => String::new(),
+    };
+    vec![date, lessons, count, status, deadline]
+}
+
+/// minutes of lateness by month and by subject, and the unjustified lessons they make up
+pub fn handle_lateness(user: &User, subj: Option<String>, args: &crate::Args) -> Res<()> {
+    let mut absences = user.get_absences((None, None))?;
+    absences.retain(is_lateness);
+    // every unjustified lateness of the year adds up, regardless of the subject
+    let total = absences.iter().map(late_minutes).sum::<u32>();
+    let unjustified = absences.iter().map(unjustified_minutes).sum::<u32>();
+    let lessons = unjustified / LATE_MINUTES_PER_LESSON;
+    let until_next = LATE_MINUTES_PER_LESSON - unjustified % LATE_MINUTES_PER_LESSON;
+    if let Some(subject) = subj {
+        filter_by_subject(&mut absences, &subject);
+    }
+    let by_month = sum_lateness(&absences, |abs| {
+        let start = abs.ora.kezdo_datum;
+        (start.year(), start.month(), start.hun_month().to_string())
+    });
+    let by_month = by_month
+        .into_iter()
+        .map(|((year, _, month), late)| Lateness {
+            of: format!("{year}. {month}"),
+            ..late
+        });
+    let by_subject = sum_lateness(&absences, |abs| abs.tantargy.nev.clone());
+    let by_subject = by_subject.into_iter().map(|(subject, late)| Lateness {
+        of: subject,
+        ..late
+    });
+    let (by_month, by_subject) = (by_month.collect::<Vec<_>>(), by_subject.collect::<Vec<_>>());
+
+    if args.machine {
+        let data = serde_json::json!({
+            "by_month": by_month, "by_subject": by_subject,
+            "total_minutes": total, "unjustified_minutes": unjustified,
+            "lessons": lessons, "minutes_until_next": until_next
+        });
+        println!("{data}");
+        return Ok(());
+    }
+    for (head, lateness) in [("hónap", by_month), ("tantárgy", by_subject)] {
+        let headers = [head, "ennyiszer", "perc", "igazolatlan perc", "órát ér"].into_iter();
+        utils::print_table(
+            &lateness,
+            headers,
+            args.reverse,
+            args.number,
+            Some(display_late),
+        )?;
+    }
+    println!(
+        "összesen {total} perc késés, ebből {unjustified} perc igazolatlan, ez {lessons} igazolatlan óra"
+    );
+    let next =
+        format!("még {until_next} perc igazolatlan késés, és újabb igazolatlan óra lesz belőle");
+    if until_next <= LATE_WARN_MINUTES {
+        println!("{}", next.yellow());
+    } else {
+        println!("{next}");
+    }
+    Ok(())
+}
+
+/// [`Lateness`] of `absences` summed up by `key`, ordered by it
+fn sum_lateness<K: Ord>(
+    absences: &[Absence],
+    key: impl Fn(&Absence) -> K,
+) -> BTreeMap<K, Lateness> {
+    let mut sums = BTreeMap::new();
+    for abs in absences {
+        let sum = sums.entry(key(abs)).or_insert(Lateness {
+            of: String::new(),
+            count: 0,
+            minutes: 0,
+            unjustified: 0,
+        });
+        sum.count += 1;
+        sum.minutes += late_minutes(abs);
+        sum.unjustified += unjustified_minutes(abs);
+    }
+    sums
+}
+
+/// minutes late in `abs`
+fn late_minutes(abs: &Absence) -> u32 {
+    abs.keses_percben.map_or(0, u32::from)
+}
+/// minutes late in `abs`, if it's not justified (yet)
+fn unjustified_minutes(abs: &Absence) -> u32 {
+    if abs.igazolt() { 0 } else { late_minutes(abs) }
+}
+
+fn display_late(late: &Lateness) -> Vec<String> {
+    let lessons = late.unjustified as f32 / LATE_MINUTES_PER_LESSON as f32;
+    vec![
+        late.of.clone(),
+        late.count.to_string(),
+        late.minutes.to_string(),
+        late.unjustified.to_string(),
+        format!("{lessons:.2}"),
+    ]
+}
+
+/// missed lessons per subject compared to the lessons held, and the total compared to the yearly limit
+pub fn handle_stats(user: &User, subj: Option<String>, args: &crate::Args) -> Res<()> {
+    let mut absences = user.get_absences((None, None))?;
+    absences.retain(|abs| !is_lateness(abs));
+    let today = Local::now().date_naive();
+    let mut lessons = user.get_timetable_between(user.school_year_start(today), today)?;
+    lessons.retain(|lsn| lsn.kezdet_idopont < Local::now() && !timetable::ignore_lesson(lsn));
+    let total = absences.len();
+    let total_level = WarningLevel::of(total as f32, YEAR_LIMIT as f32);
+    if let Some(subject) = &subj {
+        filter_by_subject(&mut absences, subject);
+        timetable::filter_by_subject(&mut lessons, subject);
+    }
+    let stats = subject_stats(&absences, &lessons);
+
+    if args.machine {
+        let data = serde_json::json!({
+            "subjects": stats, "total": total, "total_limit": YEAR_LIMIT, "total_level": total_level
+        });
+        println!("{data}");
+        return Ok(());
+    }
+    #[rustfmt::skip]
+    let headers = ["tantárgy", "igazolt", "igazolatlan", "megtartott", "hiányzás", "állapot"].into_iter();
+    utils::print_table(
+        &stats,
+        headers,
+        args.reverse,
+        args.number,
+        Some(display_stats),
+    )?;
+    println!(
+        "összesen {total} óra hiányzás a megengedett {YEAR_LIMIT}-ből: {}",
+        total_level.disp()
+    );
+    Ok(())
+}
+
+/// [`SubjectAbsences`] of each subject in `lessons` or `absences`, the most missed first
+pub fn subject_stats(absences: &[Absence], lessons: &[Lesson]) -> Vec<SubjectAbsences> {
+    let lesson_subjects = lessons.iter().map(subject_of);
+    let abs_subjects = absences.iter().map(|abs| abs.tantargy.nev.clone());
+    let mut subjects = lesson_subjects.chain(abs_subjects).collect::<Vec<_>>();
+    subjects.sort_unstable();
+    subjects.dedup();
+
+    let mut stats = subjects
+        .into_iter()
+        .map(|subject| {
+            let missed = absences.iter().filter(|abs| abs.tantargy.nev == subject);
+            let justified = missed.clone().filter(|abs| abs.igazolt()).count();
+            let unjustified = missed.count() - justified;
+            let held = lessons
+                .iter()
+                .filter(|lsn| subject_of(lsn) == subject)
+                .count();
+            let missed = (justified + unjustified) as f32;
+            let percent = (held > 0).then(|| missed / held as f32 * 100.);
+            let level = percent.map_or(WarningLevel::Fine, |p| {
+                WarningLevel::of(p / 100., SUBJECT_LIMIT)
+            });
+            SubjectAbsences {
+                subject,
+                justified,
+                unjustified,
+                held,
+                percent,
+                level,
+            }
+        })
+        .collect::<Vec<_>>();
+    stats.sort_by(|a, b| b.percent.unwrap_or(0.).total_cmp(&a.percent.unwrap_or(0.)));
+    stats
+}
+
+/// lessons remaining per subject, projected from the weekly timetable until the end of the school year,
+/// and how many more of them can be missed before the limits
+pub fn handle_projection(user: &User, subj: Option<String>, args: &crate::Args) -> Res<()> {
+    let mut absences = user.get_absences((None, None))?;
+    absences.retain(|abs| !is_lateness(abs));
+    let now = Local::now();
+    let today = now.date_naive();
+    // weekdays are counted without a calendar
+    let cal = user.get_school_year().unwrap_or_default();
+    let sunday = today.week(Weekday::Mon).last_day();
+    let mut lessons = user.get_timetable_between(user.school_year_start(today), sunday)?;
+    lessons.retain(|lsn| !timetable::ignore_lesson(lsn));
+    let mut week = full_week(&lessons, &cal);
+    let mut held = lessons;
+    held.retain(|lsn| lsn.kezdet_idopont < now);
+    let total = absences.len();
+    let total_allowed = YEAR_LIMIT.saturating_sub(total);
+    if let Some(subject) = &subj {
+        filter_by_subject(&mut absences, subject);
+        timetable::filter_by_subject(&mut held, subject);
+        timetable::filter_by_subject(&mut week, subject);
+    }
+    let projections = projections(&absences, &held, &week, &cal, now);
+
+    if args.machine {
+        let data = serde_json::json!({
+            "subjects": projections, "total": total, "total_limit": YEAR_LIMIT, "total_allowed": total_allowed
+        });
+        println!("{data}");
+        return Ok(());
+    }
+    #[rustfmt::skip]
+    let headers = ["tantárgy", "hiányzott", "megtartott", "hátralévő", "még kihagyható", "állapot"].into_iter();
+    utils::print_table(
+        &projections,
+        headers,
+        args.reverse,
+        args.number,
+        Some(display_projection),
+    )?;
+    println!(
+        "összesen {total} óra hiányzás, a {YEAR_LIMIT} órás határig még {total_allowed} óra hiányozható"
+    );
+    Ok(())
+}
+
+/// [`Projection`] of each subject, the fewest lessons to miss first
+/// `week` is the timetable of a whole week, repeated on the teaching days after `now` according to `cal`
+pub fn projections(
+    absences: &[Absence],
+    held: &[Lesson],
+    week: &[Lesson],
+    cal: &[SchoolDay],
+    now: LDateTime,
+) -> Vec<Projection> {
+    let today = now.date_naive();
+    let weekday_of = |lsn: &Lesson| lsn.kezdet_idopont.weekday();
+    // the rest of today, then the week repeated
+    let mut remaining = BTreeMap::<String, usize>::new();
+    for lsn in week.iter().filter(|lsn| weekday_of(lsn) == today.weekday()) {
+        if lsn.kezdet_idopont.time() > now.time()
+            && school_year::is_teaching_day_or_weekday(cal, today)
+        {
+            *remaining.entry(subject_of(lsn)).or_default() += 1;
+        }
+    }
+    let end = school_year::year_end_of(cal, today);
+    let days = today.iter_days().skip(1).take_while(|day| *day <= end);
+    for day in days.filter(|day| school_year::is_teaching_day_or_weekday(cal, *day)) {
+        for lsn in week.iter().filter(|lsn| weekday_of(lsn) == day.weekday()) {
+            *remaining.entry(subject_of(lsn)).or_default() += 1;
+        }
+    }
+
+    let stats = subject_stats(absences, held);
+    let subjects = stats.iter().map(|stat| stat.subject.clone());
+    let mut subjects = subjects
+        .chain(remaining.keys().cloned())
+        .collect::<Vec<_>>();
+    subjects.sort_unstable();
+    subjects.dedup();
+    let mut projections = subjects
+        .into_iter()
+        .map(|subject| {
+            let stat = stats.iter().find(|stat| stat.subject == subject);
+            let missed = stat.map_or(0, |stat| stat.justified + stat.unjustified);
+            let held = stat.map_or(0, |stat| stat.held);
+            let remaining = remaining.get(&subject).copied().unwrap_or_default();
+            let limit = (held + remaining) as f32 * SUBJECT_LIMIT;
+            let level = if limit > 0. {
+                WarningLevel::of(missed as f32, limit)
+            } else {
+                WarningLevel::Fine
+            };
+            Projection {
+                subject,
+                missed,
+                held,
+                remaining,
+                allowed: (limit.floor() as usize).saturating_sub(missed),
+                level,
+            }
+        })
+        .collect::<Vec<_>>();
+    projections.sort_by_key(|proj| proj.allowed);
+    projections
+}
+
+/// lessons of the last full teaching week in `lessons`, or of the week with the most lessons if none is full
+/// a week is full if there's teaching on each weekday according to `cal`, and there are lessons on each of them
+fn full_week(lessons: &[Lesson], cal: &[SchoolDay]) -> Vec<Lesson> {
+    let monday_of = |lsn: &Lesson| {
+        lsn.kezdet_idopont
+            .date_naive()
+            .week(Weekday::Mon)
+            .first_day()
+    };
+    let mut weeks = BTreeMap::<NaiveDate, Vec<Lesson>>::new();
+    for lsn in lessons {
+        weeks.entry(monday_of(lsn)).or_default().push(lsn.clone());
+    }
+    let is_full = |monday: NaiveDate, of_week: &[Lesson]| {
+        let mut weekdays = monday.iter_days().take(5);
+        weekdays.all(|day| {
+            let has_lessons = of_week
+                .iter()
+                .any(|lsn| lsn.kezdet_idopont.date_naive() == day);
+            has_lessons && school_year::is_teaching_day_or_weekday(cal, day)
+        })
+    };
+    let full = weeks
+        .iter()
+        .rev()
+        .find(|(monday, of_week)| is_full(**monday, of_week));
+    if let Some((monday, of_week)) = full {
+        log::info!("projecting from the week of {monday}");
+        return of_week.clone();
+    }
+    log::info!("no full teaching week, projecting from the one with the most lessons");
+    let most = weeks.into_values().max_by_key(Vec::len);
+    most.unwrap_or_default()
+}
+
+fn display_projection(proj: &Projection) -> Vec<String> {
+    vec![
+        proj.subject.clone(),
+        proj.missed.to_string(),
+        proj.held.to_string(),
+        proj.remaining.to_string(),
+        proj.allowed.to_string(),
+        proj.level.disp(),
+    ]
+}
+
+/// name of the subject of `lsn`, the same as in [`Absence::tantargy`]
+fn subject_of(lsn: &Lesson) -> String {
+    let subject = lsn.tantargy.as_ref().map(|t| t.nev.clone());
+    subject.unwrap_or(lsn.nev.clone())
+}
+
+/// Returns whether `abs` is just being late, not missing the lesson.
+pub fn is_lateness(abs: &Absence) -> bool {
+    abs.keses_percben.is_some() || abs.tipus.nev == "keses"
+}
+
+fn display_stats(stat: &SubjectAbsences) -> Vec<String> {
+    let percent = stat.percent.map(|p| format!("{p:.1}%")).unwrap_or_default();
+    vec![
+        stat.subject.clone(),
+        stat.justified.to_string(),
+        stat.unjustified.to_string(),
+        stat.held.to_string(),
+        percent,
+        stat.level.disp(),
+    ]
+}
+
 /// filter [`Abs`]ences by `subj`ect
 pub fn filter_by_subject(abss: &mut Vec<Absence>, subj: &str) {
     log::info!("filtering absences by subject: {subj}");
@@ -49,3 +727,72 @@ fn display(abs: &Absence) -> Vec<String> {
 
     vec![subj, teacher, from, to, lateness, kind]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// a lesson of `nev` on the day of June 2027, from `hour` for 45 minutes
+    fn lesson(nev: &str, day: u32, hour: u32) -> Lesson {
+        let kezdet = Local.with_ymd_and_hms(2027, 6, day, hour, 0, 0).unwrap();
+        Lesson {
+            nev: nev.into(),
+            kezdet_idopont: kezdet,
+            veg_idopont: kezdet + chrono::TimeDelta::minutes(45),
+            ..Default::default()
+        }
+    }
+    /// monday to friday of the week of 2027-06-07, and a tuesday of the week before
+    fn full_week_of_june() -> Vec<Lesson> {
+        vec![
+            lesson("matek", 7, 8),
+            lesson("fizika", 1, 8),
+            lesson("matek", 9, 8),
+            lesson("matek", 9, 12),
+            lesson("fizika", 10, 8),
+            lesson("matek", 11, 8),
+            lesson("fizika", 8, 8),
+        ]
+    }
+
+    #[test]
+    fn full_week_preferred() {
+        let mut lessons = full_week_of_june();
+        // this week, only monday so far
+        lessons.push(lesson("angol", 14, 8));
+        let mut week = full_week(&lessons, &[]);
+        week.sort_by_key(|lsn| lsn.kezdet_idopont);
+        let days = week.iter().map(|lsn| lsn.kezdet_idopont.day());
+        assert_eq!(days.collect::<Vec<_>>(), [7, 8, 9, 9, 10, 11]);
+    }
+
+    #[test]
+    fn most_lessons_without_full_week() {
+        let lessons = [
+            lesson("matek", 7, 8),
+            lesson("matek", 14, 8),
+            lesson("fizika", 15, 8),
+        ];
+        let week = full_week(&lessons, &[]);
+        assert_eq!(week, lessons[1..]);
+    }
+
+    #[test]
+    fn projected_until_year_end() {
+        let week = full_week(&full_week_of_june(), &[]);
+        // wednesday, the year ends on 2027-06-15 without a calendar
+        let now = Local.with_ymd_and_hms(2027, 6, 9, 10, 0, 0).unwrap();
+        let mut missed = Absence::default();
+        missed.tantargy.nev = "matek".into();
+        let projections = projections(&[missed], &week, &week, &[], now);
+
+        // matek: 12:00 today, then friday and monday; fizika: thursday and tuesday
+        let of = |subj: &str| projections.iter().find(|p| p.subject == subj).unwrap();
+        let (matek, fizika) = (of("matek"), of("fizika"));
+        assert_eq!((matek.missed, matek.held, matek.remaining), (1, 4, 3));
+        assert_eq!((fizika.missed, fizika.held, fizika.remaining), (0, 2, 2));
+        // floor(30% of 4 + 3) - 1 and floor(30% of 2 + 2) - 0
+        assert_eq!((matek.allowed, fizika.allowed), (1, 1));
+    }
+}
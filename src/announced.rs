@@ -1,17 +1,36 @@
 //! Announced tests
 
-use crate::{time::MyDate, user::User, utils};
+use crate::{seen, time::MyDate, user::User, utils};
 use ekreta::{AnnouncedTest, Res};
 
-pub fn handle(past: bool, user: &User, subj: Option<String>, args: &crate::Args) -> Res<()> {
-    #[rustfmt::skip]
-    let from = if past { None } else { Some(chrono::Local::now().date_naive()) };
-    let mut all_announced = user.get_tests((from, None))?;
+pub fn handle(
+    past: bool,
+    user: &User,
+    subj: Option<String>,
+    new: bool,
+    mark_seen: bool,
+    args: &crate::Args,
+) -> Res<()> {
+    // all of them, so that past ones are seen as well when first tracking them
+    let mut all_announced = user.get_tests((None, None))?;
+    let seen = user.seen_of(&all_announced)?;
+    if mark_seen {
+        user.mark_seen(&all_announced)?;
+    }
+    if !past {
+        let today = chrono::Local::now().date_naive();
+        all_announced.retain(|ancd| ancd.datum.date_naive() >= today);
+    }
     if let Some(subject) = subj {
         filter_by_subject(&mut all_announced, &subject);
     }
+    let is_new = |ancd: &AnnouncedTest| !seen.contains(&ancd.uid);
+    if new {
+        all_announced.retain(is_new);
+    }
     let headers = ["téma", "tantárgy", "dátum", "mód", "tanár"].into_iter();
-    let dix = if args.machine { None } else { Some(display) };
+    let disp = |ancd: &AnnouncedTest| seen::mark_new(display(ancd), is_new(ancd));
+    let dix = if args.machine { None } else { Some(disp) };
     utils::print_table(&all_announced, headers, args.reverse, args.number, dix)
 }
 
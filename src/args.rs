@@ -52,6 +52,80 @@ pub enum Command {
         /// show current lesson if any
         #[arg(short, long, default_value_t = false)]
         current: bool,
+
+        /// show the whole week (Monday to Friday) of the day as a grid
+        #[arg(short, long, default_value_t = false, conflicts_with = "current")]
+        week: bool,
+
+        /// show changes of forecoming lessons: substitutions, cancellations, room or time changes
+        #[arg(long, default_value_t = false, conflicts_with_all = ["current", "week"])]
+        changes: bool,
+
+        /// keep redrawing the current lesson with a countdown until interrupted
+        #[arg(long, default_value_t = false, requires = "current")]
+        watch: bool,
+
+        /// refetch lessons this often while watching, in seconds
+        #[arg(long, default_value_t = 300, requires = "watch")]
+        refresh: u64,
+    },
+
+    /// when is the next lesson of a subject, with a teacher or in a room
+    Next {
+        /// filter by `subject`
+        subject: Option<String>,
+        /// filter by teacher, substitute teachers included
+        #[arg(short, long)]
+        teacher: Option<String>,
+        /// filter by room
+        #[arg(short, long)]
+        room: Option<String>,
+    },
+
+    /// time ranges when all the given users are free: no lessons, or cancelled ones only
+    Free {
+        /// which day to check: `(+|-)n` (`n` is the number of days added to today) or [YYYY-][MM-][DD]
+        #[arg(value_parser = crate::timetable::parse_day)]
+        day: Option<chrono::NaiveDate>,
+        /// users (by name or ID) to check, all saved ones by default
+        #[arg(short, long, value_delimiter = ',')]
+        users: Vec<String>,
+        /// check the whole week (Monday to Friday) of the day
+        #[arg(short, long, default_value_t = false)]
+        week: bool,
+        /// look for free time from this time of the day: HH:MM
+        #[arg(long, default_value = "07:00", value_parser = crate::free::parse_time)]
+        from: chrono::NaiveTime,
+        /// look for free time until this time of the day: HH:MM
+        #[arg(long, default_value = "18:00", value_parser = crate::free::parse_time)]
+        until: chrono::NaiveTime,
+    },
+
+    /// topics of the lessons of a subject throughout the school year
+    #[clap(visible_alias = "tp")]
+    Topics {
+        /// the subject to show, like `evals --subject`
+        subject: String,
+    },
+
+    /// current or next lesson in a single line for status bars, eg. waybar, i3blocks, polybar, tmux
+    /// only uses cached data, so it's cheap to call often
+    Status {
+        /// print json of text, tooltip and class, as waybar's custom module expects it
+        #[arg(long)]
+        waybar: bool,
+    },
+
+    /// the school-year calendar: breaks, special days and the number of teaching days left
+    #[clap(visible_alias = "cal")]
+    Calendar,
+
+    /// the half-year or end-of-year report card: final grades, magatartás and szorgalom
+    #[clap(visible_alias = "rc")]
+    ReportCard {
+        /// which report card to show, the latest one by default
+        #[arg(value_enum)]
+        term: Option<crate::report_card::Term>,
     },
 
     /// evaluations/grades the user received
@@ -66,9 +140,50 @@ pub enum Command {
         /// calculate average
         #[arg(short, long, default_value_t = false)]
         average: bool,
-        /// ghost evals
-        #[arg(requires = "average")]
-        ghost: Vec<u8>,
+        /// calculate average of each subject separately
+        #[arg(short, long, requires = "average", default_value_t = false)]
+        by_subject: bool,
+        /// chart of the average and the grades of each subject over time
+        #[arg(short, long, default_value_t = false, conflicts_with = "average")]
+        plot: bool,
+        /// distribution and statistics of grades, overall and per subject
+        #[arg(long, default_value_t = false, conflicts_with_all = ["average", "plot"])]
+        stats: bool,
+        /// list the fewest grades needed to reach this average in `subject`
+        #[arg(long, requires = "subject", conflicts_with_all = ["average", "plot", "stats"])]
+        target: Option<f32>,
+        /// weight(s) of the grades to come in percent, for `target`
+        #[arg(
+            long,
+            value_delimiter = ',',
+            default_value = "100",
+            requires = "target"
+        )]
+        weight: Vec<u16>,
+        /// maximum number of grades to come, for `target`, 20 at most
+        #[arg(
+            long,
+            default_value_t = 5,
+            requires = "target",
+            value_parser = clap::value_parser!(u8).range(1..=20)
+        )]
+        max: u8,
+        /// ghost evals, written as `GRADE[xMULTIPLIER|@PERCENT%][:KIND][,DATE]`
+        /// eg. `5`, `5x2`, `4@200%`, `3:tz` or `4:dg,2026-11-03`
+        /// kinds: tz (témazáró), dg (dolgozat), rd (röpdolgozat), f (felelet)
+        /// also loaded from `~/.config/rsfilc/ghosts/<subject>` with `subject`, matched like `subject`,
+        /// except for `stats` and `plot`
+        #[arg(value_parser = crate::evals::parse_ghost)]
+        ghost: Vec<crate::evals::Ghost>,
+        /// history of modified and deleted evals
+        #[arg(long, default_value_t = false, conflicts_with_all = ["average", "plot", "stats", "target"])]
+        history: bool,
+        /// only show the ones not seen yet
+        #[arg(long, default_value_t = false, conflicts_with_all = ["average", "plot", "stats", "target", "history"])]
+        new: bool,
+        /// mark all of them as seen
+        #[arg(long, default_value_t = false, conflicts_with_all = ["average", "plot", "stats", "target", "history"])]
+        mark_seen: bool,
     },
 
     /// messages the user either received or sent
@@ -79,6 +194,12 @@ pub enum Command {
         notes: bool,
         /// id of the message to render
         id: Option<isize>,
+        /// with `notes`: only show the ones not seen yet
+        #[arg(long, default_value_t = false, requires = "notes")]
+        new: bool,
+        /// with `notes`: mark all of them as seen
+        #[arg(long, default_value_t = false, requires = "notes")]
+        mark_seen: bool,
     },
 
     /// information about lessons the user missed
@@ -90,6 +211,21 @@ pub enum Command {
         /// count the number of absences
         #[arg(short, long, default_value_t = false)]
         count: bool,
+        /// missed lessons per subject compared to the ones held, with warnings near the limits
+        #[arg(long, default_value_t = false, conflicts_with = "count")]
+        stats: bool,
+        /// missed lessons grouped by day, with the deadline of justifying them
+        #[arg(short, long, default_value_t = false, conflicts_with_all = ["count", "stats"])]
+        grouped: bool,
+        /// minutes of lateness by month and by subject, and the unjustified lessons they make up
+        #[arg(short, long, default_value_t = false, conflicts_with_all = ["count", "stats", "grouped"])]
+        late: bool,
+        /// lessons remaining this school year per subject, and how many more of them can be missed
+        #[arg(long, default_value_t = false, conflicts_with_all = ["count", "stats", "grouped", "late"])]
+        remaining: bool,
+        /// months of the school year as a calendar, days colored by the number of lessons missed
+        #[arg(long, default_value_t = false, conflicts_with_all = ["count", "stats", "grouped", "late", "remaining"])]
+        calendar: bool,
     },
 
     /// information about forecoming exams/tests
@@ -101,6 +237,12 @@ pub enum Command {
         /// show tests from the past as well
         #[arg(short, long, default_value_t = false)]
         past: bool,
+        /// only show the ones not seen yet
+        #[arg(long, default_value_t = false)]
+        new: bool,
+        /// mark all of them as seen
+        #[arg(long, default_value_t = false)]
+        mark_seen: bool,
     },
 
     /// managing users of this program, listing if nothing specified
@@ -129,6 +271,21 @@ pub enum Command {
         #[arg(short, long, name = "SCHOOL_PROPERTY")]
         search: Option<String>,
     },
+    /// export lessons and announced tests to other formats
+    Export {
+        /// the format to export to
+        #[arg(value_enum)]
+        format: crate::export::ExportFormat,
+        /// first day to export: `(+|-)n` or [YYYY-][MM-][DD], today by default
+        #[arg(short, long, value_parser = crate::timetable::parse_day)]
+        from: Option<chrono::NaiveDate>,
+        /// last day to export, same format as `from`, 4 weeks after `from` by default
+        #[arg(short, long, value_parser = crate::timetable::parse_day)]
+        to: Option<chrono::NaiveDate>,
+        /// write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
     /// show the time of next server downtime
     NextDowntime,
     /// guided renaming
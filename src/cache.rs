@@ -1,4 +1,7 @@
-use crate::{Res, paths::cache_path};
+use crate::{
+    Res,
+    paths::{cache_path, data_path, history_path},
+};
 use chrono::{DateTime, Local};
 use std::fs::{self, File};
 use std::io::Write;
@@ -33,14 +36,57 @@ pub fn load(userid: &str, kind: &str) -> Option<(DateTime<Local>, String)> {
 
     Some((t.into(), c))
 }
+/// save data that isn't cache to disk, replacing the old file only once it's written
+pub fn store_data(userid: &str, kind: &str, content: &str) -> Res<()> {
+    let dp = data_path(userid, kind).ok_or("couldn't get data path")?;
+    let tmp = dp.with_extension("json.tmp");
+    log::info!("storing data to {dp:?}");
+    fs::write(&tmp, content)?;
+    fs::rename(tmp, dp)?;
+    Ok(())
+}
+
+/// load data that isn't cache from disk, `None` if it's not stored yet
+/// # Errors
+/// it exists, but couldn't be read
+pub fn load_data(userid: &str, kind: &str) -> Res<Option<String>> {
+    let dp = data_path(userid, kind).ok_or("couldn't get data path")?;
+    log::info!("loading data from {dp:?}");
+    if !dp.exists() {
+        return Ok(None);
+    }
+    Ok(Some(fs::read_to_string(dp)?))
+}
+
+/// append `lines` to the history of `kind`, which is never overwritten
+pub fn append_history(userid: &str, kind: &str, lines: &[String]) -> Res<()> {
+    let hp = history_path(userid, kind).ok_or("couldn't get history path")?;
+    let mut f = File::options().create(true).append(true).open(&hp)?;
+    log::info!("appending {} lines to history at {hp:?}", lines.len());
+    for line in lines {
+        writeln!(f, "{line}")?;
+    }
+    Ok(())
+}
+
+/// load the history of `kind`, a line for each entry
+pub fn load_history(userid: &str, kind: &str) -> Vec<String> {
+    let Some(hp) = history_path(userid, kind) else {
+        return vec![];
+    };
+    log::info!("loading history from {hp:?}");
+    let content = fs::read_to_string(hp).unwrap_or_default();
+    content.lines().map(str::to_string).collect()
+}
+
 /// delete all cache and logs as well
 pub fn delete_dir(userid: &str) -> Res<()> {
-    if let Some(cd) = crate::paths::cache_dir(userid) {
-        if cd.exists() {
-            log::warn!("deleting cache dir");
-            fs::remove_dir_all(cd)?;
-            log::info!("done");
-        }
+    if let Some(cd) = crate::paths::cache_dir(userid)
+        && cd.exists()
+    {
+        log::warn!("deleting cache dir");
+        fs::remove_dir_all(cd)?;
+        log::info!("done");
     }
     Ok(())
 }
@@ -1,17 +1,58 @@
+// `User` holds an `Account`, which has a request agent, but `Ord` only uses ids
+#![allow(clippy::mutable_key_type)]
+
 use crate::{Res, User};
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeSet, path::PathBuf};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
 
 pub const APP_NAME: &str = "rsfilc";
 const CONFIG_NAME: &str = "config";
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub default_userid: String,
     pub users: BTreeSet<User>,
     pub rename: BTreeSet<[String; 2]>,
+    /// how many days to look ahead for lessons at most
+    pub lookahead_days: u16,
+    /// how averages are rounded into final grades by default
+    pub rounding: Rounding,
+    /// rounding by a part of the subject name or by the institute code of the school, takes precedence over `rounding`
+    pub rounding_of: BTreeMap<String, Rounding>,
+    /// absences should be justified within this many teaching days
+    pub justify_days: u16,
+}
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            default_userid: String::new(),
+            users: BTreeSet::new(),
+            rename: BTreeSet::new(),
+            lookahead_days: 28,
+            rounding: Rounding::default(),
+            rounding_of: BTreeMap::new(),
+            justify_days: 5,
+        }
+    }
 }
 impl Config {
+    /// [`Rounding`] of `subject` at school of `schoolid`, the institute code
+    /// a key matches `subject` if it's part of its name, the longest matching key wins
+    pub fn rounding_for(&self, subject: &str, schoolid: &str) -> Rounding {
+        let subject = subject.to_lowercase();
+        let by_subject = self
+            .rounding_of
+            .iter()
+            .filter(|(key, _)| !key.is_empty() && subject.contains(&key.to_lowercase()))
+            .max_by_key(|(key, _)| key.chars().count())
+            .map(|(_, rounding)| rounding);
+        let by_school = self.rounding_of.get(schoolid);
+        by_subject.or(by_school).copied().unwrap_or(self.rounding)
+    }
     pub fn load() -> Res<Config> {
         Ok(confy::load(APP_NAME, CONFIG_NAME)?)
     }
@@ -51,3 +92,70 @@ impl Config {
         Ok(confy::get_configuration_file_path(APP_NAME, CONFIG_NAME)?)
     }
 }
+
+/// how an average is rounded into a final grade
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rounding {
+    /// fractional part from which to round up, eg. 0.5 or 0.6
+    pub threshold: f32,
+    /// average from which it's a pass, if it differs from `threshold`, eg. 1.75
+    pub pass_from: Option<f32>,
+}
+impl Default for Rounding {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            pass_from: None,
+        }
+    }
+}
+impl Rounding {
+    /// averages from which the grade is 2, 3, 4 and 5
+    fn boundaries(&self) -> [f32; 4] {
+        let pass = self.pass_from.unwrap_or(1. + self.threshold);
+        let t = self.threshold;
+        [pass, 2. + t, 3. + t, 4. + t]
+    }
+    /// the final grade `avg` is rounded to
+    pub fn grade(&self, avg: f32) -> u8 {
+        let passed = self.boundaries().iter().filter(|b| avg >= **b).count();
+        1 + passed as u8
+    }
+    /// Returns whether `avg` is within 0.1 of a boundary between two grades.
+    pub fn near_boundary(&self, avg: f32) -> bool {
+        self.boundaries().iter().any(|b| (avg - b).abs() < 0.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn threshold(threshold: f32) -> Rounding {
+        Rounding {
+            threshold,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rounding_lookup() {
+        let conf = Config {
+            rounding: threshold(0.5),
+            rounding_of: BTreeMap::from([
+                ("matematika".into(), threshold(0.6)),
+                ("emelt matematika".into(), threshold(0.7)),
+                ("Fizika".into(), threshold(0.8)),
+                ("klik035046001".into(), threshold(0.4)),
+            ]),
+            ..Default::default()
+        };
+        let of = |subject, school| conf.rounding_for(subject, school).threshold;
+        assert_eq!(of("Matematika", "klik035046001"), 0.6);
+        assert_eq!(of("Emelt matematika", "klik035046001"), 0.7);
+        assert_eq!(of("fizika", "other"), 0.8);
+        assert_eq!(of("Történelem", "klik035046001"), 0.4);
+        assert_eq!(of("Történelem", "other"), 0.5);
+    }
+}
@@ -1,37 +1,698 @@
 //! evaluations/grades the user received
 
-use crate::{time::MyDate, user::User, utils};
-use ekreta::{Evaluation, Res};
+use crate::{
+    config::Config, config::Rounding, paths, plot, seen, time::MyDate, timetable::parse_day,
+    user::User, utils,
+};
+use chrono::{Local, NaiveDate, TimeDelta};
+use ekreta::{Endpoint, Evaluation, LDateTime, Res};
 use log::info;
+use serde::{Deserialize, Serialize};
+use yansi::Paint;
+
+/// average of a subject
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubjectAverage {
+    pub subject: String,
+    /// weighted average, see [`calc_average`]
+    pub average: f32,
+    /// number of grades counted
+    pub count: usize,
+    /// the latest grade
+    pub latest: Option<u8>,
+    /// the change of `average` compared to 30 days ago
+    pub change: Option<f32>,
+    /// the final grade `average` rounds to
+    pub predicted: u8,
+    /// the latest final grade already received, évvégi or félévi
+    pub actual: Option<u8>,
+    /// whether `average` is within 0.1 of a boundary between two grades
+    pub near_boundary: bool,
+    /// the average after each grade, oldest first
+    pub trend: Vec<f32>,
+    /// average of the class in this subject, see [`ClassAverage`]
+    pub class_average: Option<f32>,
+    /// `average` minus `class_average`
+    pub class_difference: Option<f32>,
+}
+
+/// a change of an already received eval, noticed when fetching
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalChange {
+    /// when it was noticed
+    pub noticed: LDateTime,
+    /// the eval after the change, or the deleted one
+    pub eval: Evaluation,
+    pub kind: EvalChangeKind,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EvalChangeKind {
+    /// grade: old, new
+    Value(String, String),
+    /// weight in percent: old, new
+    Weight(u16, u16),
+    /// date of the eval: old, new
+    Date(LDateTime, LDateTime),
+    Deleted,
+}
+
+/// average of the student and of the class in a subject, as calculated by Kréta
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ClassAverage {
+    pub uid: String,
+    pub tantargy: SubjectRef,
+    pub tanulo_atlag: Option<f32>,
+    pub osztaly_csoport_atlag: Option<f32>,
+    pub osztaly_csoport_atlagtol_valo_elteres: Option<f32>,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct SubjectRef {
+    pub uid: String,
+    pub nev: String,
+}
+impl Endpoint for ClassAverage {
+    /// uid of the education task of the class, see [`ekreta::Class::oktatas_nevelesi_feladat`]
+    type Args = String;
+
+    fn path(_args: &Self::Args) -> String {
+        "/ellenorzo/V3/Sajat/Ertekelesek/Atlagok/OsztalyAtlagok".into()
+    }
+
+    fn query(input: Self::Args) -> Vec<(&'static str, String)> {
+        vec![("oktatasiNevelesiFeladatUid", input)]
+    }
+}
+
+/// a grade and the average right after receiving it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrendPoint {
+    pub date: LDateTime,
+    pub grade: u8,
+    pub average: f32,
+}
+/// [`TrendPoint`]s of a subject
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubjectTrend {
+    pub subject: String,
+    pub points: Vec<TrendPoint>,
+}
+
+/// distribution and statistics of the grades of a subject
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GradeStats {
+    /// `None` for all subjects together
+    pub subject: Option<String>,
+    /// number of grades from 1 to 5
+    pub counts: [usize; 5],
+    pub median: f32,
+    /// the most frequent grade, the higher one on ties
+    pub mode: u8,
+    pub std_dev: f32,
+    /// share of grades not weighing 100%
+    pub weighted_share: f32,
+}
+impl GradeStats {
+    /// [`GradeStats`] of `evals`, `None` if there's no grade that counts towards the average
+    fn new(subject: Option<String>, evals: &[&Evaluation]) -> Option<Self> {
+        let mut grades = evals
+            .iter()
+            .filter_map(|e| e.szam_ertek)
+            .collect::<Vec<_>>();
+        if grades.is_empty() {
+            return None;
+        }
+        grades.sort_unstable();
+        let mut counts = [0; 5];
+        for grade in grades.iter().filter(|g| (1..=5).contains(*g)) {
+            counts[usize::from(*grade) - 1] += 1;
+        }
+        let mid = grades.len() / 2;
+        let median = if grades.len() % 2 == 0 {
+            f32::from(grades[mid - 1] + grades[mid]) / 2.
+        } else {
+            f32::from(grades[mid])
+        };
+        let (mode_idx, _) = counts.iter().enumerate().max_by_key(|(_, n)| **n)?;
+        let n = grades.len() as f32;
+        let mean = grades.iter().map(|g| f32::from(*g)).sum::<f32>() / n;
+        let variance = grades
+            .iter()
+            .map(|g| (f32::from(*g) - mean).powi(2))
+            .sum::<f32>()
+            / n;
+        let weighted = evals.iter().filter(|e| e.szorzo() != 1.).count();
+        Some(GradeStats {
+            subject,
+            counts,
+            median,
+            mode: mode_idx as u8 + 1,
+            std_dev: variance.sqrt(),
+            weighted_share: weighted as f32 / n,
+        })
+    }
+}
+/// widest bar of the histogram in [`GradeStats`]
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// height of the chart of a subject in characters
+const PLOT_HEIGHT: usize = 6;
+/// width of the vertical axis of the chart, see [`plot::line_chart`]
+const PLOT_AXIS_WIDTH: usize = 3;
+/// number of trend points shown in the sparkline of the averages table
+const SPARK_LEN: usize = 12;
+
+/// jelleg of hypothetical evals, see [`Ghost`]
+const GHOST: &str = "Ghost";
+/// abbreviated kinds of ghost evals: (abbreviation, description, weight in percent)
+const GHOST_KINDS: [(&str, &str, u16); 4] = [
+    ("tz", "Témazáró", 200),
+    ("dg", "Dolgozat", 100),
+    ("rd", "Röpdolgozat", 100),
+    ("f", "Felelet", 100),
+];
+
+/// a hypothetical eval, written as `GRADE[xMULTIPLIER|@PERCENT%][:KIND][,DATE]`
+/// eg. `5`, `5x2`, `4@200%`, `3:tz` or `4:dg,2026-11-03`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ghost {
+    pub grade: u8,
+    /// in percent, like [`Evaluation::suly_szazalek_erteke`]
+    pub weight: u16,
+    /// description of the kind, see [`GHOST_KINDS`]
+    pub kind: Option<String>,
+    pub date: Option<NaiveDate>,
+}
+impl Ghost {
+    /// an [`Evaluation`] of `subj` with the data of this [`Ghost`], marked hypothetical
+    fn to_eval(&self, subj: &str, idx: usize) -> Evaluation {
+        let mut eval = Evaluation {
+            uid: format!("{idx},{GHOST}"),
+            jelleg: GHOST.into(),
+            szam_ertek: Some(self.grade),
+            szoveges_ertek: self.grade.to_string(),
+            suly_szazalek_erteke: Some(self.weight),
+            ..Default::default()
+        };
+        let when = self.date.and_then(crate::time::local_midnight);
+        eval.keszites_datuma = when.unwrap_or(Local::now());
+        eval.rogzites_datuma = eval.keszites_datuma;
+        eval.tantargy.nev = subj.to_string();
+        eval.tipus.nev = "evkozi_jegy_ertekeles".into();
+        if let Some(kind) = &self.kind {
+            let mut how = eval.tipus.clone();
+            how.leiras.clone_from(kind);
+            eval.r#mod = Some(how);
+        }
+        eval
+    }
+}
+/// parse a [`Ghost`] eval
+pub fn parse_ghost(ghost: &str) -> Result<Ghost, String> {
+    let (rest, date) = match ghost.split_once(',') {
+        Some((rest, date)) => (rest, Some(parse_day(date)?)),
+        None => (ghost, None),
+    };
+    let (rest, kind) = match rest.split_once(':') {
+        Some((rest, abbrev)) => {
+            let kind = GHOST_KINDS.iter().find(|(ab, _, _)| *ab == abbrev);
+            let valid = GHOST_KINDS.map(|(ab, _, _)| ab).join(", ");
+            (
+                rest,
+                Some(kind.ok_or(format!("unknown kind: {abbrev}, try: {valid}"))?),
+            )
+        }
+        None => (rest, None),
+    };
+    let (grade, weight) = if let Some((grade, percent)) = rest.split_once('@') {
+        let percent = percent.trim_end_matches('%').parse::<u16>();
+        (
+            grade,
+            Some(percent.map_err(|e| format!("invalid weight: {e}"))?),
+        )
+    } else if let Some((grade, multiplier)) = rest.split_once('x') {
+        let multiplier = multiplier.parse::<f32>();
+        let multiplier = multiplier.map_err(|e| format!("invalid multiplier: {e}"))?;
+        let percent = (multiplier * 100.).round();
+        if !(0. ..=f32::from(u16::MAX)).contains(&percent) {
+            return Err(format!("invalid multiplier: {multiplier}"));
+        }
+        (grade, Some(percent as u16))
+    } else {
+        (rest, None)
+    };
+    let grade = grade
+        .parse::<u8>()
+        .map_err(|e| format!("invalid grade: {e}"))?;
+    if !(1..=5).contains(&grade) {
+        return Err(format!("grade should be between 1 and 5, not {grade}"));
+    }
+    let weight = weight.or(kind.map(|(_, _, weight)| *weight)).unwrap_or(100);
+    if weight == 0 {
+        return Err("weight should be above 0".into());
+    }
+    let kind = kind.map(|(_, desc, _)| desc.to_string());
+    Ok(Ghost {
+        grade,
+        weight,
+        kind,
+        date,
+    })
+}
+
+/// [`Ghost`]s stored in the ghost files of `subj`, see [`paths::ghost_dir`]
+/// A file belongs to `subj` if its name contains it, just like in [`filter_by_subject`],
+/// or if it's named after `real_name`, the full name of the subject.
+fn ghosts_from_files(subj: &str, real_name: &str) -> Vec<Ghost> {
+    let Some(files) = paths::ghost_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return vec![];
+    };
+    let (subj, real_name) = (subj.to_lowercase(), real_name.to_lowercase());
+    let of_subj = |name: &str| {
+        let name = name.to_lowercase();
+        name.contains(&subj) || name == real_name
+    };
+    let mut paths = files
+        .filter_map(|file| file.ok().map(|file| file.path()))
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|n| of_subj(&n.to_string_lossy()))
+        })
+        .collect::<Vec<_>>();
+    paths.sort_unstable();
+
+    let mut ghosts = vec![];
+    for path in paths {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        info!("loading ghosts from {path:?}");
+        let lines = content
+            .lines()
+            .map(|l| l.split('#').next().unwrap_or_default());
+        let parsed = lines.flat_map(str::split_whitespace).map(parse_ghost);
+        ghosts.extend(
+            parsed.filter_map(|ghost| ghost.inspect_err(|e| log::warn!("{path:?}: {e}")).ok()),
+        );
+    }
+    ghosts
+}
+
+/// Add `ghosts` of `subj` to `evals`, and the ones in its ghost file if `ghost_file`
+fn add_ghosts(evals: &mut Vec<Evaluation>, subj: Option<&str>, ghosts: &[Ghost], ghost_file: bool) {
+    let subj_name = if let Some(subj) = subj {
+        // use the real name of the subject, if there's any eval of it
+        let real = evals.iter().find(|e| {
+            let name = e.tantargy.nev.to_lowercase();
+            name.contains(&subj.to_lowercase())
+        });
+        real.map_or(subj.to_string(), |e| e.tantargy.nev.clone())
+    } else {
+        String::new()
+    };
+    let from_file = subj
+        .filter(|_| ghost_file)
+        .map(|subj| ghosts_from_files(subj, &subj_name))
+        .unwrap_or_default();
+    let ghosts = from_file.iter().chain(ghosts).enumerate();
+    evals.extend(ghosts.map(|(i, ghost)| ghost.to_eval(&subj_name, i)));
+    evals.sort_by_key(|e| e.keszites_datuma);
+}
+
+/// Returns whether `eval` is a hypothetical [`Ghost`].
+pub fn is_ghost(eval: &Evaluation) -> bool {
+    eval.jelleg == GHOST
+}
 
 pub fn handle(
     user: &User,
     filter: Option<String>,
     subj: Option<String>,
-    ghost: &[u8],
-    avg: bool,
+    ghost: &[Ghost],
+    new: bool,
+    mark_seen: bool,
     args: &crate::Args,
 ) -> Res<()> {
     let mut evals = user.get_evals((None, None))?;
     info!("got evals");
-    if let Some(kind) = filter {
-        filter_by_kind_or_title(&mut evals, &kind);
+    let seen = user.seen_of(&evals)?;
+    if mark_seen {
+        user.mark_seen(&evals)?;
     }
-    if let Some(subject) = subj {
-        filter_by_subject(&mut evals, &subject);
-    }
-    if avg {
-        let avg = calc_average(&evals, ghost);
-        println!("Average: {avg:.2}");
-
-        return Ok(());
+    filter_evals(&mut evals, filter, subj.as_deref(), ghost, true);
+    let is_new = |eval: &Evaluation| !is_ghost(eval) && !seen.contains(&eval.uid);
+    if new {
+        evals.retain(is_new);
     }
     #[rustfmt::skip]
     let headers = ["téma", "jegy", "tantárgy", "típus", "tanár", "időpont"];
-    let disp = if args.machine { None } else { Some(display) };
+    let disp = |eval: &Evaluation| seen::mark_new(display(eval), is_new(eval));
+    let disp = if args.machine { None } else { Some(disp) };
     utils::print_table(&evals, headers.into_iter(), args.reverse, args.number, disp)
 }
 
+/// weighted average, and the predicted final grade if it's of a single subject
+pub fn handle_average(
+    user: &User,
+    conf: &Config,
+    filter: Option<String>,
+    subj: Option<String>,
+    ghost: &[Ghost],
+) -> Res<()> {
+    let evals = filtered_evals(user, filter, subj.as_deref(), ghost, true)?;
+    let avg = calc_average(&evals);
+    println!("Average: {avg:.2}");
+    if let Some(subj) = evals.first().map(|e| &e.tantargy.nev)
+        && evals.iter().all(|e| &e.tantargy.nev == subj)
+    {
+        let rounding = conf.rounding_for(subj, &user.account.schoolid);
+        let near = if rounding.near_boundary(avg) {
+            " (határeset)"
+        } else {
+            ""
+        };
+        println!("Predicted: {}{near}", rounding.grade(avg));
+    }
+    Ok(())
+}
+
+/// averages of each subject
+pub fn handle_averages(
+    user: &User,
+    conf: &Config,
+    filter: Option<String>,
+    subj: Option<String>,
+    ghost: &[Ghost],
+    args: &crate::Args,
+) -> Res<()> {
+    let evals = filtered_evals(user, filter, subj.as_deref(), ghost, true)?;
+    let schoolid = &user.account.schoolid;
+    let mut avgs = subject_averages(&evals, |subj| conf.rounding_for(subj, schoolid));
+    match user.get_class_averages() {
+        Ok(class_avgs) => add_class_averages(&mut avgs, &class_avgs),
+        Err(e) => log::warn!("couldn't get class averages: {e:?}"),
+    }
+    #[rustfmt::skip]
+    let headers = ["tantárgy", "átlag", "db", "utolsó", "30 napja", "jósolt", "lezárt", "menet", "osztály", "eltérés"];
+    let disp = if args.machine {
+        None
+    } else {
+        Some(display_avg)
+    };
+    utils::print_table(&avgs, headers.into_iter(), args.reverse, args.number, disp)
+}
+
+/// distribution and statistics of grades, overall and per subject
+pub fn handle_stats(
+    user: &User,
+    filter: Option<String>,
+    subj: Option<String>,
+    ghost: &[Ghost],
+    args: &crate::Args,
+) -> Res<()> {
+    // hypothetical grades only if asked for explicitly, not from the ghost file
+    let evals = filtered_evals(user, filter, subj.as_deref(), ghost, false)?;
+    let counted = evals.iter().filter(|e| counts(e)).collect::<Vec<_>>();
+    let mut stats = vec![];
+    stats.extend(GradeStats::new(None, &counted));
+    for subject in subjects(&evals) {
+        let of_subj = counted.iter().filter(|e| e.tantargy.nev == subject);
+        let of_subj = of_subj.copied().collect::<Vec<_>>();
+        stats.extend(GradeStats::new(Some(subject), &of_subj));
+    }
+    if args.machine {
+        println!("{}", serde_json::to_string(&stats)?);
+        return Ok(());
+    }
+    let width = ascii_table::AsciiTable::default().max_width();
+    let width = width.saturating_sub(12).clamp(5, HISTOGRAM_WIDTH);
+    for stat in stats.iter().take(args.number) {
+        display_stats(stat, width);
+    }
+    Ok(())
+}
+
+fn display_stats(stat: &GradeStats, width: usize) {
+    let name = stat.subject.as_deref().unwrap_or("összes");
+    let count = stat.counts.iter().sum::<usize>();
+    println!("{} ({count} jegy)", name.bold());
+    let most = stat.counts.iter().max().copied().unwrap_or(1).max(1);
+    for (grade, n) in stat.counts.iter().enumerate().rev() {
+        let bar = "█".repeat((n * width).div_ceil(most));
+        println!("  {} │{bar} {n}", grade + 1);
+    }
+    let weighted = (stat.weighted_share * 100.).round();
+    println!(
+        "  medián: {}, módusz: {}, szórás: {:.2}, súlyozott: {weighted}%\n",
+        stat.median, stat.mode, stat.std_dev
+    );
+}
+
+/// the history of modified and deleted evals
+pub fn handle_history(user: &User, args: &crate::Args) -> Res<()> {
+    // to notice the latest changes as well
+    if let Err(e) = user.get_evals((None, None)) {
+        log::warn!("couldn't get evals: {e:?}");
+    }
+    let history = user.get_eval_history();
+    if history.is_empty() && !args.machine {
+        println!("eddig semmi sem változott");
+        return Ok(());
+    }
+    let headers = ["észlelve", "tantárgy", "téma", "változás", "kelte"].into_iter();
+    let disp = if args.machine {
+        None
+    } else {
+        Some(display_change)
+    };
+    utils::print_table(&history, headers, args.reverse, args.number, disp)
+}
+
+/// Changes of `cached` evals compared to the `fetched` ones of the same uid.
+/// Only evals since `year_start` are compared, older ones aren't fetched anyway.
+pub fn diff_evals(
+    cached: &[Evaluation],
+    fetched: &[Evaluation],
+    year_start: NaiveDate,
+) -> Vec<EvalChange> {
+    // Kréta returning nothing at all is much more likely a glitch than every eval deleted
+    if fetched.is_empty() {
+        return vec![];
+    }
+    let noticed = Local::now();
+    let mut changes = vec![];
+    let this_year = cached
+        .iter()
+        .filter(|e| e.keszites_datuma.date_naive() >= year_start);
+    for old in this_year {
+        let change = |eval: &Evaluation, kind| EvalChange {
+            noticed,
+            eval: eval.clone(),
+            kind,
+        };
+        let Some(new) = fetched.iter().find(|e| e.uid == old.uid) else {
+            changes.push(change(old, EvalChangeKind::Deleted));
+            continue;
+        };
+        if grade_of(old) != grade_of(new) {
+            let kind = EvalChangeKind::Value(grade_of(old), grade_of(new));
+            changes.push(change(new, kind));
+        }
+        let weight = |e: &Evaluation| e.suly_szazalek_erteke.unwrap_or(100);
+        if weight(old) != weight(new) {
+            let kind = EvalChangeKind::Weight(weight(old), weight(new));
+            changes.push(change(new, kind));
+        }
+        if old.keszites_datuma != new.keszites_datuma {
+            let kind = EvalChangeKind::Date(old.keszites_datuma, new.keszites_datuma);
+            changes.push(change(new, kind));
+        }
+    }
+    changes
+}
+
+/// the grade of `eval` as shown
+fn grade_of(eval: &Evaluation) -> String {
+    if let Some(num) = eval.szam_ertek {
+        num.to_string()
+    } else {
+        eval.szoveges_ertek.clone()
+    }
+}
+
+fn display_change(change: &EvalChange) -> Vec<String> {
+    let noticed = change.noticed.pretty();
+    let subject = change.eval.tantargy.nev.clone();
+    let topic = change.eval.tema.clone().unwrap_or_default();
+    let what = match &change.kind {
+        EvalChangeKind::Value(old, new) => format!("jegy: {old} -> {new}"),
+        EvalChangeKind::Weight(old, new) => format!("súly: {old}% -> {new}%"),
+        EvalChangeKind::Date(old, new) if new < old => {
+            format!("visszadátumozva: {} -> {}", old.pretty(), new.pretty())
+        }
+        EvalChangeKind::Date(old, new) => {
+            format!("átdátumozva: {} -> {}", old.pretty(), new.pretty())
+        }
+        EvalChangeKind::Deleted => "törölve".red().to_string(),
+    };
+    let when = change.eval.keszites_datuma.pretty();
+    vec![noticed, subject, topic, what, when]
+}
+
+/// Fill [`SubjectAverage::class_average`] and [`SubjectAverage::class_difference`] of `avgs` from `class_avgs` by subject name
+fn add_class_averages(avgs: &mut [SubjectAverage], class_avgs: &[ClassAverage]) {
+    for avg in avgs {
+        let of_subj = class_avgs
+            .iter()
+            .find(|ca| ca.tantargy.nev.to_lowercase() == avg.subject.to_lowercase());
+        avg.class_average = of_subj.and_then(|ca| ca.osztaly_csoport_atlag);
+        // own average might differ from Kréta's, eg. with ghosts, Kréta's difference is the fallback
+        let kretas = of_subj.and_then(|ca| ca.osztaly_csoport_atlagtol_valo_elteres);
+        avg.class_difference = avg.class_average.map(|c| avg.average - c).or(kretas);
+    }
+}
+
+/// chart of the running average and the grades of each subject
+pub fn handle_plot(
+    user: &User,
+    filter: Option<String>,
+    subj: Option<String>,
+    ghost: &[Ghost],
+    args: &crate::Args,
+) -> Res<()> {
+    // hypothetical grades only if asked for explicitly, not from the ghost file
+    let evals = filtered_evals(user, filter, subj.as_deref(), ghost, false)?;
+    let trends = subjects(&evals).into_iter().map(|subject| {
+        let of_subj = evals.iter().filter(|e| e.tantargy.nev == subject);
+        let points = trend(&of_subj.cloned().collect::<Vec<_>>());
+        SubjectTrend { subject, points }
+    });
+    let trends = trends.filter(|t| !t.points.is_empty()).collect::<Vec<_>>();
+    if args.machine {
+        println!("{}", serde_json::to_string(&trends)?);
+        return Ok(());
+    }
+    let width = ascii_table::AsciiTable::default().max_width();
+    let width = width.saturating_sub(PLOT_AXIS_WIDTH).max(10);
+    for trend in trends.iter().take(args.number) {
+        let avg = trend.points.last().map_or(0., |p| p.average);
+        println!("{} {avg:.2}", trend.subject.bold());
+        for row in plot_trend(&trend.points, width) {
+            println!("{row}");
+        }
+        let first = trend
+            .points
+            .first()
+            .map(|p| p.date.pretty())
+            .unwrap_or_default();
+        let last = trend
+            .points
+            .last()
+            .map(|p| p.date.pretty())
+            .unwrap_or_default();
+        let gap = width.saturating_sub(first.chars().count() + last.chars().count());
+        println!(
+            "{}{first}{}{last}\n",
+            " ".repeat(PLOT_AXIS_WIDTH),
+            " ".repeat(gap)
+        );
+    }
+    Ok(())
+}
+
+/// braille chart of the averages in `points` with the grades over it, `width` characters wide
+fn plot_trend(points: &[TrendPoint], width: usize) -> Vec<String> {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return vec![];
+    };
+    let dots = width * 2;
+    let span = (last.date - first.date).num_seconds().max(1) as f32;
+    let dot_of = |date: LDateTime| {
+        let since_first = (date - first.date).num_seconds() as f32;
+        (since_first / span * (dots - 1) as f32).round() as usize
+    };
+    let mut line = vec![None; dots];
+    for (i, point) in points.iter().enumerate() {
+        let until = points.get(i + 1).map_or(dots, |next| dot_of(next.date));
+        for y in line.iter_mut().take(until).skip(dot_of(point.date)) {
+            *y = Some(point.average);
+        }
+    }
+    let grades = points.iter().map(|p| plot::Point {
+        x: dot_of(p.date),
+        y: f32::from(p.grade),
+        label: p.grade.to_string(),
+    });
+    plot::line_chart(&line, &grades.collect::<Vec<_>>(), PLOT_HEIGHT, 1., 5.)
+}
+
+/// [`TrendPoint`]s of the grades in `evals` that count towards the average, oldest first
+fn trend(evals: &[Evaluation]) -> Vec<TrendPoint> {
+    let mut counted = evals.iter().filter(|e| counts(e)).collect::<Vec<_>>();
+    counted.sort_by_key(|e| e.keszites_datuma);
+    let mut points = vec![];
+    let (mut sum, mut count) = (0., 0.);
+    for eval in counted {
+        let grade = eval.szam_ertek.unwrap_or(0);
+        sum += f32::from(grade) * eval.szorzo();
+        count += eval.szorzo();
+        let average = sum / count;
+        points.push(TrendPoint {
+            date: eval.keszites_datuma,
+            grade,
+            average,
+        });
+    }
+    points
+}
+
+/// names of the subjects in `evals`, ordered
+fn subjects(evals: &[Evaluation]) -> Vec<String> {
+    let mut subjects = evals
+        .iter()
+        .map(|eval| eval.tantargy.nev.clone())
+        .collect::<Vec<_>>();
+    subjects.sort_unstable();
+    subjects.dedup();
+    subjects
+}
+
+/// Returns whether `eval` counts towards the average.
+fn counts(eval: &Evaluation) -> bool {
+    !eval.evvegi() && !eval.felevi() && eval.szam_ertek.is_some()
+}
+
+/// evals filtered by `kind` and `subj`, with `ghosts` added, see [`filter_evals`]
+fn filtered_evals(
+    user: &User,
+    kind: Option<String>,
+    subj: Option<&str>,
+    ghosts: &[Ghost],
+    ghost_file: bool,
+) -> Res<Vec<Evaluation>> {
+    let mut evals = user.get_evals((None, None))?;
+    info!("got evals");
+    filter_evals(&mut evals, kind, subj, ghosts, ghost_file);
+    Ok(evals)
+}
+/// Filter `evals` by `kind` and `subj`, then add `ghosts`, and the ones in the ghost file if `ghost_file`.
+fn filter_evals(
+    evals: &mut Vec<Evaluation>,
+    kind: Option<String>,
+    subj: Option<&str>,
+    ghosts: &[Ghost],
+    ghost_file: bool,
+) {
+    if let Some(kind) = kind {
+        filter_by_kind_or_title(evals, &kind);
+    }
+    if let Some(subject) = subj {
+        filter_by_subject(evals, subject);
+    }
+    add_ghosts(evals, subj, ghosts, ghost_file);
+}
+
 /// Filter `evals` by `kind`
 pub fn filter_by_kind_or_title(evals: &mut Vec<Evaluation>, filter: &str) {
     let filter = filter.to_lowercase();
@@ -59,28 +720,305 @@ pub fn filter_by_subject(evals: &mut Vec<Evaluation>, subj: &str) {
     });
 }
 
-/// Calculate average of `evals` and `ghosts` evals
-pub fn calc_average(evals: &[Evaluation], ghosts: &[u8]) -> f32 {
+/// Calculate average of `evals`
+pub fn calc_average(evals: &[Evaluation]) -> f32 {
     log::info!("calculating average for evals");
-    let evals = evals
+    let (sum, count) = weighted_sum(evals);
+    sum / count
+}
+
+/// weighted sum and sum of weights of the grades in `evals` that count towards the average
+fn weighted_sum(evals: &[Evaluation]) -> (f32, f32) {
+    let evals = evals.iter().filter(|eval| counts(eval));
+    evals.fold((0., 0.), |(sum, count), cur| {
+        let weight = cur.szorzo();
+        let grade = f32::from(cur.szam_ertek.unwrap_or(0));
+        (sum + grade * weight, count + weight)
+    })
+}
+
+/// a grade yet to be received
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FutureGrade {
+    pub grade: u8,
+    /// in percent, like 200 for a témazáró
+    pub weight: u16,
+}
+impl FutureGrade {
+    fn szorzo(&self) -> f32 {
+        f32::from(self.weight) / 100.
+    }
+}
+
+/// the average to reach with grades to come, see [`solve_target`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Target<'a> {
+    pub average: f32,
+    /// of the grades to come, in percent
+    pub weights: &'a [u16],
+    /// number of grades to come at most
+    pub max: usize,
+}
+
+/// what to get to reach the average of `target` in `subj`
+pub fn handle_target(
+    user: &User,
+    filter: Option<String>,
+    subj: &str,
+    ghost: &[Ghost],
+    target: &Target,
+    args: &crate::Args,
+) -> Res<()> {
+    let evals = filtered_evals(user, filter, Some(subj), ghost, true)?;
+    let (average, max) = (target.average, target.max);
+    if calc_average(&evals) >= average {
+        println!("{average:.2} már megvan, juhé!");
+        return Ok(());
+    }
+    let (sum, count) = weighted_sum(&evals);
+
+    let combos = solve_target(sum, count, target);
+    if combos.is_empty() && !args.machine {
+        println!("{average:.2} nem érhető el {max} jeggyel");
+        return Ok(());
+    }
+    if combos.is_empty() {
+        println!("[]");
+        return Ok(());
+    }
+    let headers = ["ezek kellenek"].into_iter();
+    let disp = (!args.machine).then_some(|combo: &Vec<_>| display_combo(combo));
+    utils::print_table(&combos, headers, args.reverse, args.number, disp)
+}
+
+/// Minimal combinations of the fewest future grades with the weights of `target`,
+/// that bring the weighted average of `sum` / `count` to at least the average of `target`.
+/// A combination is minimal if lowering any grade of it would miss the target.
+/// Without any grades yet, it's any single grade that reaches the target, with any of the weights.
+/// At most [`MAX_COMBOS`] of them, empty if it's not possible with `target.max` grades.
+pub fn solve_target(sum: f32, count: f32, target: &Target) -> Vec<Vec<FutureGrade>> {
+    let Some(&top_weight) = target.weights.iter().max() else {
+        return vec![];
+    };
+    let mut weights = target.weights.to_vec();
+    weights.sort_unstable();
+    weights.dedup();
+    if count <= 0. {
+        let reaches = |grade: &u8| f32::from(*grade) >= target.average - TARGET_EPS;
+        let grades = weights.iter().flat_map(|&weight| {
+            let reaching = (1..=5).filter(reaches);
+            reaching.map(move |grade| vec![FutureGrade { grade, weight }])
+        });
+        return grades.take(MAX_COMBOS).collect();
+    }
+    // each grade adds (grade - target) * weight, they need to add up to this
+    let need = target.average * count - sum;
+    // the best one can get is all 5s of the highest weight
+    let best = (5. - target.average) * f32::from(top_weight) / 100.;
+    let n = if need <= 0. {
+        0
+    } else if best <= 0. {
+        return vec![];
+    } else {
+        // rounding errors shouldn't hide an exact hit
+        ((need - TARGET_EPS) / best).ceil().max(1.) as usize
+    };
+    if n == 0 || n > target.max {
+        return vec![];
+    }
+
+    let options = weights
         .iter()
-        .filter(|eval| !eval.evvegi() && !eval.felevi() && eval.szam_ertek.is_some());
+        .flat_map(|&weight| (1..=5).map(move |grade| FutureGrade { grade, weight }))
+        .collect::<Vec<_>>();
+    let mut combos = vec![];
+    let mut combo = vec![];
+    let search = Search {
+        options: &options,
+        average: target.average,
+        need,
+    };
+    search.combos(&mut combo, 0, 0., n, &mut combos);
+    combos
+}
+
+/// at most this many combinations are listed by [`solve_target`]
+const MAX_COMBOS: usize = 20;
+/// tolerance of the sums of [`solve_target`]
+const TARGET_EPS: f32 = 1e-4;
+
+/// depth-first search of the minimal combinations of [`solve_target`]
+struct Search<'a> {
+    /// ordered by weight, then by grade
+    options: &'a [FutureGrade],
+    average: f32,
+    need: f32,
+}
+impl Search<'_> {
+    /// what `grade` adds towards `self.need`
+    fn gain(&self, grade: &FutureGrade) -> f32 {
+        (f32::from(grade.grade) - self.average) * grade.szorzo()
+    }
+    /// Add minimal combinations of `left` more grades from `self.options[from..]` to `combo` into `found`.
+    /// `gained` is what `combo` adds so far.
+    fn combos(
+        &self,
+        combo: &mut Vec<FutureGrade>,
+        from: usize,
+        gained: f32,
+        left: usize,
+        found: &mut Vec<Vec<FutureGrade>>,
+    ) {
+        if found.len() >= MAX_COMBOS {
+            return;
+        }
+        if left == 0 {
+            // lowering a grade costs its weight, unless it's a 1 already
+            let lowerable = combo
+                .iter()
+                .filter(|g| g.grade > 1)
+                .map(FutureGrade::szorzo);
+            let lightest = lowerable.fold(f32::INFINITY, f32::min);
+            if gained >= self.need - TARGET_EPS && gained - lightest < self.need - TARGET_EPS {
+                found.push(combo.clone());
+            }
+            return;
+        }
+        let rest = &self.options[from..];
+        let gains = rest.iter().map(|g| self.gain(g));
+        let (lowest, highest) = gains.fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), g| {
+            (lo.min(g), hi.max(g))
+        });
+        let left_f = left as f32;
+        // can't reach the target anymore
+        if gained + highest * left_f < self.need - TARGET_EPS {
+            return;
+        }
+        // the lightest lowerable grade could be lowered and the target would still be reached
+        let lowerable = combo
+            .iter()
+            .filter(|g| g.grade > 1)
+            .map(FutureGrade::szorzo);
+        let lightest = lowerable.fold(f32::INFINITY, f32::min);
+        if gained + lowest * left_f - lightest >= self.need - TARGET_EPS {
+            return;
+        }
+        for (i, grade) in rest.iter().enumerate() {
+            combo.push(*grade);
+            self.combos(combo, from + i, gained + self.gain(grade), left - 1, found);
+            combo.pop();
+        }
+    }
+}
 
-    // filter it, so only valid grades retain
-    let ghosts = ghosts.iter().filter(|g| *g > &0 && *g <= &5);
+fn display_combo(combo: &[FutureGrade]) -> Vec<String> {
+    let grades = combo.iter().rev().map(|g| {
+        if g.weight == 100 {
+            g.grade.to_string()
+        } else {
+            format!("{}@{}%", g.grade, g.weight)
+        }
+    });
+    vec![grades.collect::<Vec<_>>().join(", ")]
+}
 
-    let sum = evals.clone().fold(0., |sum, cur| {
-        sum + f32::from(cur.szam_ertek.unwrap_or(0)) * cur.szorzo()
-    }) + ghosts.clone().fold(0., |sum, num| sum + f32::from(*num));
+/// [`SubjectAverage`] of each subject in `evals`, ordered by subject name
+pub fn subject_averages(
+    evals: &[Evaluation],
+    rounding: impl Fn(&str) -> Rounding,
+) -> Vec<SubjectAverage> {
+    let month_ago = Local::now() - TimeDelta::days(30);
 
-    let count = evals.clone().fold(0., |sum, cur| sum + cur.szorzo()) + ghosts.count() as f32;
+    let mut avgs = vec![];
+    for subject in subjects(evals) {
+        let of_subj = evals.iter().filter(|eval| eval.tantargy.nev == subject);
+        // évvégi takes precedence over félévi
+        let actual = of_subj
+            .clone()
+            .filter(|eval| eval.evvegi() || eval.felevi())
+            .max_by_key(|eval| (eval.evvegi(), eval.keszites_datuma))
+            .and_then(|eval| eval.szam_ertek);
+        let mut of_subj = of_subj
+            .filter(|eval| counts(eval))
+            .cloned()
+            .collect::<Vec<_>>();
+        if of_subj.is_empty() {
+            continue;
+        }
+        of_subj.sort_unstable_by_key(|eval| eval.keszites_datuma);
+        let average = calc_average(&of_subj);
+        let latest = of_subj.last().and_then(|eval| eval.szam_ertek);
+        let count = of_subj.len();
+        let trend = trend(&of_subj).into_iter().map(|p| p.average).collect();
+        of_subj.retain(|eval| eval.keszites_datuma <= month_ago);
+        let change = (!of_subj.is_empty()).then(|| average - calc_average(&of_subj));
+        let rounding = rounding(&subject);
+        avgs.push(SubjectAverage {
+            predicted: rounding.grade(average),
+            near_boundary: rounding.near_boundary(average),
+            subject,
+            average,
+            count,
+            latest,
+            change,
+            actual,
+            trend,
+            class_average: None,
+            class_difference: None,
+        });
+    }
+    avgs
+}
 
-    sum / count
+fn display_avg(avg: &SubjectAverage) -> Vec<String> {
+    let subject = avg.subject.clone();
+    let average = format!("{:.2}", avg.average);
+    let (average, predicted) = if avg.near_boundary {
+        let predicted = format!("{}?", avg.predicted).yellow().to_string();
+        (average.yellow().to_string(), predicted)
+    } else {
+        (average, avg.predicted.to_string())
+    };
+    let actual = avg.actual.map(|a| a.to_string()).unwrap_or_default();
+    let count = avg.count.to_string();
+    let latest = avg.latest.map(|l| l.to_string()).unwrap_or_default();
+    let change = match avg.change {
+        Some(change) if change >= 0.005 => format!("{change:+.2}").green().to_string(),
+        Some(change) if change <= -0.005 => format!("{change:+.2}").red().to_string(),
+        Some(_) => "=".to_string(),
+        None => String::new(),
+    };
+    let recent = &avg.trend[avg.trend.len().saturating_sub(SPARK_LEN)..];
+    let min = recent.iter().copied().fold(5., f32::min);
+    let max = recent.iter().copied().fold(1., f32::max);
+    let trend = plot::sparkline(recent, min, max);
+    let class_avg = avg
+        .class_average
+        .map(|c| format!("{c:.2}"))
+        .unwrap_or_default();
+    let diff = match avg.class_difference {
+        Some(diff) if diff >= 0.005 => format!("{diff:+.2}").green().to_string(),
+        Some(diff) if diff <= -0.005 => format!("{diff:+.2}").red().to_string(),
+        Some(_) => "=".to_string(),
+        None => String::new(),
+    };
+    vec![
+        subject, average, count, latest, change, predicted, actual, trend, class_avg, diff,
+    ]
 }
 
 fn display(eval: &Evaluation) -> Vec<String> {
-    let desc = eval.tema.clone().unwrap_or_default();
-    let grade = if let Some(num) = eval.szam_ertek {
+    let desc = if is_ghost(eval) {
+        "feltételezett".italic().dim().to_string()
+    } else {
+        eval.tema.clone().unwrap_or_default()
+    };
+    let grade = if let Some(num) = eval.szam_ertek
+        && is_ghost(eval)
+        && eval.szorzo() != 1.
+    {
+        format!("{num}@{}%", eval.suly_szazalek_erteke.unwrap_or(100))
+    } else if let Some(num) = eval.szam_ertek {
         num.to_string()
     } else {
         eval.szoveges_ertek.clone()
@@ -95,3 +1033,208 @@ fn display(eval: &Evaluation) -> Vec<String> {
         desc, grade, subj_name, how_desc, /* kind_name, */ teacher, when,
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn target(average: f32, weights: &[u16], max: usize) -> Target<'_> {
+        Target {
+            average,
+            weights,
+            max,
+        }
+    }
+    fn grades(combo: &[FutureGrade]) -> Vec<(u8, u16)> {
+        combo.iter().map(|g| (g.grade, g.weight)).collect()
+    }
+    /// every combination of `n` grades, checked one by one
+    fn brute_force(sum: f32, count: f32, target: &Target, n: usize) -> Vec<Vec<(u8, u16)>> {
+        let options = target
+            .weights
+            .iter()
+            .flat_map(|&weight| (1..=5).map(move |grade| (grade, weight)))
+            .collect::<Vec<_>>();
+        let reaches = |combo: &[(u8, u16)]| {
+            let added = combo
+                .iter()
+                .map(|&(g, w)| f32::from(g) * f32::from(w) / 100.);
+            let added_count = combo.iter().map(|&(_, w)| f32::from(w) / 100.);
+            let avg = (sum + added.sum::<f32>()) / (count + added_count.sum::<f32>());
+            avg >= target.average - 1e-4
+        };
+        let mut combos = vec![vec![]];
+        for _ in 0..n {
+            let longer = combos.iter().flat_map(|combo: &Vec<usize>| {
+                let from = combo.last().copied().unwrap_or(0);
+                (from..options.len()).map(move |i| [combo.clone(), vec![i]].concat())
+            });
+            combos = longer.collect();
+        }
+        let combos = combos
+            .into_iter()
+            .map(|idxs| idxs.iter().map(|&i| options[i]).collect::<Vec<_>>());
+        combos
+            .filter(|combo| {
+                reaches(combo)
+                    && (0..combo.len()).all(|i| {
+                        let mut lower = combo.clone();
+                        lower[i].0 -= 1;
+                        lower[i].0 == 0 || !reaches(&lower)
+                    })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ghost_weights() {
+        assert_eq!(parse_ghost("4x2").map(|g| g.weight), Ok(200));
+        assert_eq!(parse_ghost("4@50%").map(|g| g.weight), Ok(50));
+        assert_eq!(parse_ghost("3:tz").map(|g| g.weight), Ok(200));
+        for invalid in ["4x0", "4@0%", "4x-1", "4x1000", "4xnan"] {
+            assert!(
+                parse_ghost(invalid).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn single_grade_enough() {
+        let combos = solve_target(3., 1., &target(4., &[100], 5));
+        assert_eq!(
+            combos.iter().map(|c| grades(c)).collect::<Vec<_>>(),
+            [[(5, 100)]]
+        );
+    }
+
+    #[test]
+    fn already_reached() {
+        assert!(solve_target(5., 1., &target(4., &[100], 5)).is_empty());
+    }
+
+    #[test]
+    fn target_without_grades() {
+        let combos = solve_target(0., 0., &target(4., &[100, 200], 5));
+        assert_eq!(
+            combos.iter().map(|c| grades(c)).collect::<Vec<_>>(),
+            [[(4, 100)], [(5, 100)], [(4, 200)], [(5, 200)]]
+        );
+        assert!(solve_target(0., 0., &target(5.5, &[100], 5)).is_empty());
+    }
+
+    #[test]
+    fn unreachable() {
+        // can't get to 5 once there's a 4
+        assert!(solve_target(4., 1., &target(5., &[100, 200], 20)).is_empty());
+        assert!(solve_target(1., 1., &target(5.5, &[100], 20)).is_empty());
+        // would need 7 grades
+        assert!(solve_target(1., 1., &target(4.5, &[100], 5)).is_empty());
+        assert!(solve_target(1., 1., &target(4.5, &[100], 6)).is_empty());
+        assert!(!solve_target(1., 1., &target(4.5, &[100], 7)).is_empty());
+    }
+
+    #[test]
+    fn many_options_stay_fast() {
+        let weights = [50, 100, 200];
+        // unreachable, known without searching
+        assert!(solve_target(20., 10., &target(4.9, &weights, 20)).is_empty());
+        // reachable with 15 grades at most
+        let combos = solve_target(30., 10., &target(4.5, &weights, 20));
+        assert!(!combos.is_empty() && combos.len() <= MAX_COMBOS);
+        assert!(combos.iter().all(|combo| combo.len() == combos[0].len()));
+    }
+
+    #[test]
+    fn same_as_brute_force() {
+        let cases = [
+            (7., 2., 4., vec![100]),
+            (7., 2., 4., vec![100, 200]),
+            (10., 3., 3.8, vec![50, 100, 200]),
+            (13., 4., 4.2, vec![100, 300]),
+            (4.5, 1.5, 3.5, vec![50]),
+        ];
+        for (sum, count, average, weights) in cases {
+            let target = target(average, &weights, 5);
+            let mut got = solve_target(sum, count, &target)
+                .iter()
+                .map(|c| grades(c))
+                .collect::<Vec<_>>();
+            let n = got.first().map_or(0, Vec::len);
+            assert!(n > 0, "{sum}/{count} -> {average} should be reachable");
+            let mut expected = brute_force(sum, count, &target, n);
+            got.sort();
+            expected.sort();
+            assert!(expected.len() <= MAX_COMBOS);
+            assert_eq!(got, expected, "{sum}/{count} -> {average} with {weights:?}");
+            assert!(brute_force(sum, count, &target, n - 1).is_empty());
+        }
+    }
+
+    /// an eval with `uid`, made at noon of `day` of October 2026
+    fn dated_eval(uid: &str, grade: u8, weight: u16, day: u32) -> Evaluation {
+        let made = Local.with_ymd_and_hms(2026, 10, day, 12, 0, 0).unwrap();
+        Evaluation {
+            uid: uid.into(),
+            szam_ertek: Some(grade),
+            szoveges_ertek: grade.to_string(),
+            suly_szazalek_erteke: Some(weight),
+            keszites_datuma: made,
+            ..Default::default()
+        }
+    }
+    fn kinds(changes: &[EvalChange]) -> Vec<(String, EvalChangeKind)> {
+        let kind = |c: &EvalChange| (c.eval.uid.clone(), c.kind.clone());
+        changes.iter().map(kind).collect()
+    }
+    fn sept_first() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 9, 1).unwrap()
+    }
+
+    #[test]
+    fn diff_unchanged() {
+        let evals = [dated_eval("a", 4, 100, 5), dated_eval("b", 3, 200, 6)];
+        assert!(diff_evals(&evals, &evals, sept_first()).is_empty());
+    }
+
+    #[test]
+    fn diff_value_weight_date() {
+        let cached = [dated_eval("a", 4, 100, 5), dated_eval("b", 3, 200, 6)];
+        let fetched = [dated_eval("a", 2, 200, 5), dated_eval("b", 3, 200, 8)];
+        let (old_date, new_date) = (cached[1].keszites_datuma, fetched[1].keszites_datuma);
+        let expected = [
+            ("a".into(), EvalChangeKind::Value("4".into(), "2".into())),
+            ("a".into(), EvalChangeKind::Weight(100, 200)),
+            ("b".into(), EvalChangeKind::Date(old_date, new_date)),
+        ];
+        assert_eq!(
+            kinds(&diff_evals(&cached, &fetched, sept_first())),
+            expected
+        );
+    }
+
+    #[test]
+    fn diff_deleted() {
+        let cached = [dated_eval("a", 4, 100, 5), dated_eval("b", 3, 200, 6)];
+        let fetched = [dated_eval("b", 3, 200, 6)];
+        let changes = diff_evals(&cached, &fetched, sept_first());
+        assert_eq!(kinds(&changes), [("a".into(), EvalChangeKind::Deleted)]);
+        assert_eq!(changes[0].eval, cached[0]);
+    }
+
+    #[test]
+    fn diff_empty_fetch_ignored() {
+        let cached = [dated_eval("a", 4, 100, 5)];
+        assert!(diff_evals(&cached, &[], sept_first()).is_empty());
+    }
+
+    #[test]
+    fn diff_before_year_start_ignored() {
+        let cached = [dated_eval("a", 4, 100, 5), dated_eval("b", 3, 100, 20)];
+        let fetched = [dated_eval("b", 5, 100, 20)];
+        let year_start = NaiveDate::from_ymd_opt(2026, 10, 10).unwrap();
+        let expected = [("b".into(), EvalChangeKind::Value("3".into(), "5".into()))];
+        assert_eq!(kinds(&diff_evals(&cached, &fetched, year_start)), expected);
+    }
+}
@@ -0,0 +1,220 @@
+//! exporting data to other formats, eg. iCalendar
+
+use crate::user::User;
+use chrono::{Local, NaiveDate, TimeDelta, Utc};
+use ekreta::{AnnouncedTest, LDateTime, Lesson, Res};
+use log::info;
+use std::{fmt::Write, fs, path::PathBuf};
+
+/// max length of an iCalendar content line in octets, CRLF excluded
+const ICS_LINE_LEN: usize = 75;
+
+/// formats data can be exported to
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// iCalendar (RFC 5545): lessons and announced tests
+    Ics,
+}
+
+pub fn handle(
+    user: &User,
+    format: ExportFormat,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    output: Option<PathBuf>,
+) -> Res<()> {
+    let from = from.unwrap_or(Local::now().date_naive());
+    let to = to.unwrap_or(from + TimeDelta::weeks(4));
+    if to < from {
+        return Err(format!("invalid interval: {from} is after {to}").into());
+    }
+    info!("exporting to {format:?} from {from} to {to}");
+    let exported = match format {
+        ExportFormat::Ics => {
+            let lessons = user.get_timetable_between(from, to)?;
+            let tests = user.get_tests((Some(from), Some(to)))?;
+            ics(&user.account.schoolid, &lessons, &tests)
+        }
+    };
+    if let Some(path) = output {
+        fs::write(&path, exported)?;
+        info!("exported to {}", path.display());
+        println!("exported to {}", path.display());
+    } else {
+        print!("{exported}");
+    }
+    Ok(())
+}
+
+/// create an iCalendar out of `lessons` and announced `tests`
+pub fn ics(schoolid: &str, lessons: &[Lesson], tests: &[AnnouncedTest]) -> String {
+    let now = ics_time(Local::now());
+    let mut cal = String::new();
+    let mut line = |content: String| push_folded(&mut cal, &content);
+    line("BEGIN:VCALENDAR".into());
+    line("VERSION:2.0".into());
+    line(format!(
+        "PRODID:-//rsfilc//rsfilc {}//HU",
+        env!("CARGO_PKG_VERSION")
+    ));
+    line("CALSCALE:GREGORIAN".into());
+    line("METHOD:PUBLISH".into());
+
+    for lsn in lessons.iter().filter(|lsn| !lsn.kamu_smafu()) {
+        let mut desc = vec![];
+        if let Some(teacher) = &lsn.tanar_neve {
+            desc.push(format!("tanár: {teacher}"));
+        }
+        if let Some(sub_teacher) = &lsn.helyettes_tanar_neve {
+            desc.push(format!("helyettes: {sub_teacher}"));
+        }
+        if let Some(topic) = &lsn.tema {
+            desc.push(format!("téma: {topic}"));
+        }
+        let status = if lsn.cancelled() {
+            "CANCELLED"
+        } else {
+            "CONFIRMED"
+        };
+
+        line("BEGIN:VEVENT".into());
+        line(format!(
+            "UID:{}",
+            ics_uid("lesson", &lesson_id(lsn), schoolid)
+        ));
+        line(format!("DTSTAMP:{now}"));
+        line(format!("DTSTART:{}", ics_time(lsn.kezdet_idopont)));
+        line(format!("DTEND:{}", ics_time(lsn.veg_idopont)));
+        line(format!("SUMMARY:{}", ics_text(&lsn.nev)));
+        if let Some(room) = &lsn.terem_neve {
+            line(format!("LOCATION:{}", ics_text(room)));
+        }
+        if !desc.is_empty() {
+            line(format!("DESCRIPTION:{}", ics_text(&desc.join("\n"))));
+        }
+        line(format!("STATUS:{status}"));
+        if let Some(modified) = lsn.utolso_modositas.and_local_timezone(Local).earliest() {
+            line(format!("LAST-MODIFIED:{}", ics_time(modified)));
+        }
+        line("END:VEVENT".into());
+    }
+
+    for test in tests {
+        let same_lsn = |lsn: &&Lesson| {
+            lsn.kezdet_idopont.date_naive() == test.datum.date_naive()
+                && lsn.oraszam == test.orarendi_ora_oraszama
+                && !lsn.kamu_smafu()
+        };
+        let summary = format!("{}: {}", test.modja.leiras, test.tantargy_neve);
+
+        line("BEGIN:VEVENT".into());
+        line(format!("UID:{}", ics_uid("test", &test.uid, schoolid)));
+        line(format!("DTSTAMP:{now}"));
+        if let Some(lsn) = lessons.iter().find(same_lsn) {
+            line(format!("DTSTART:{}", ics_time(lsn.kezdet_idopont)));
+            line(format!("DTEND:{}", ics_time(lsn.veg_idopont)));
+        } else {
+            let day = test.datum.date_naive();
+            line(format!("DTSTART;VALUE=DATE:{}", day.format("%Y%m%d")));
+            let next_day = day + TimeDelta::days(1);
+            line(format!("DTEND;VALUE=DATE:{}", next_day.format("%Y%m%d")));
+        }
+        line(format!("SUMMARY:{}", ics_text(&summary)));
+        let mut desc = format!("tanár: {}", test.rogzito_tanar_neve);
+        if let Some(topic) = &test.temaja {
+            _ = write!(&mut desc, "\ntéma: {topic}");
+        }
+        line(format!("DESCRIPTION:{}", ics_text(&desc)));
+        line("END:VEVENT".into());
+    }
+
+    line("END:VCALENDAR".into());
+    cal
+}
+
+/// id of `lsn` that stays the same when Kréta gives it a new uid, eg. once it's held
+fn lesson_id(lsn: &Lesson) -> String {
+    let day = lsn.kezdet_idopont.format("%Y%m%d");
+    let num = lsn.oraszam.map_or_else(
+        || lsn.kezdet_idopont.format("%H%M").to_string(),
+        |n| n.to_string(),
+    );
+    let subject = lsn.subject_id().unwrap_or(&lsn.nev);
+    format!("{day}-{num}-{subject}")
+}
+
+/// stable, globally unique id of an event, derived from the id of the item
+fn ics_uid(kind: &str, uid: &str, schoolid: &str) -> String {
+    let uid = uid.replace(|c: char| !c.is_ascii_alphanumeric() && c != '-', "-");
+    format!("{kind}-{uid}@{schoolid}.e-kreta.hu")
+}
+
+/// UTC date-time, as iCalendar likes it
+fn ics_time(time: LDateTime) -> String {
+    time.with_timezone(&Utc)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+/// escape `text` to be a valid iCalendar TEXT value
+fn ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// push `content` to `cal` as a content line, folded to [`ICS_LINE_LEN`] octets
+fn push_folded(cal: &mut String, content: &str) {
+    let mut line_len = 0;
+    for ch in content.chars() {
+        if line_len + ch.len_utf8() > ICS_LINE_LEN {
+            cal.push_str("\r\n ");
+            line_len = 1;
+        }
+        cal.push(ch);
+        line_len += ch.len_utf8();
+    }
+    cal.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_text() {
+        assert_eq!(ics_text("a;b,c\\d"), "a\\;b\\,c\\\\d");
+        assert_eq!(ics_text("egy\r\nkettő\nhárom"), "egy\\nkettő\\nhárom");
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let mut cal = String::new();
+        push_folded(&mut cal, &"a".repeat(80));
+        assert_eq!(cal, format!("{}\r\n {}\r\n", "a".repeat(75), "a".repeat(5)));
+    }
+
+    #[test]
+    fn folds_on_char_boundary() {
+        let mut cal = String::new();
+        let content = format!("{}é", "a".repeat(74));
+        push_folded(&mut cal, &content);
+        assert_eq!(cal, format!("{}\r\n é\r\n", "a".repeat(74)));
+        assert!(cal.split("\r\n").all(|line| line.len() <= ICS_LINE_LEN));
+    }
+
+    #[test]
+    fn short_lines_unfolded() {
+        let mut cal = String::new();
+        push_folded(&mut cal, "BEGIN:VCALENDAR");
+        assert_eq!(cal, "BEGIN:VCALENDAR\r\n");
+    }
+
+    #[test]
+    fn uid_sanitized() {
+        let uid = ics_uid("lesson", "20261012-3-matematika/fakt", "klik0123");
+        assert_eq!(uid, "lesson-20261012-3-matematika-fakt@klik0123.e-kreta.hu");
+    }
+}
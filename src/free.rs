@@ -0,0 +1,97 @@
+//! time ranges when several users are free at once
+
+use crate::{config::Config, timetable, user::User, utils};
+use chrono::{Local, NaiveDate, NaiveTime, TimeDelta, Weekday};
+use ekreta::Res;
+use log::info;
+use serde::Serialize;
+
+/// a time range on a day, when everyone is free
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FreeTime {
+    pub date: NaiveDate,
+    pub from: NaiveTime,
+    pub to: NaiveTime,
+}
+
+pub fn handle(
+    conf: &Config,
+    day: Option<NaiveDate>,
+    users: &[String],
+    week: bool,
+    from: NaiveTime,
+    until: NaiveTime,
+    args: &crate::Args,
+) -> Res<()> {
+    let users = if users.is_empty() {
+        conf.users.iter().cloned().collect()
+    } else {
+        let load = |who: &String| User::load(conf, who).ok_or(format!("invalid user ({who})"));
+        users.iter().map(load).collect::<Result<Vec<_>, _>>()?
+    };
+    let day = day.unwrap_or(Local::now().date_naive());
+    let (first, last) = if week {
+        let monday = day.week(Weekday::Mon).first_day();
+        (monday, monday + TimeDelta::days(4))
+    } else {
+        (day, day)
+    };
+    info!("looking for common free time of {} users", users.len());
+
+    let lessons_of = users
+        .iter()
+        .map(|user| {
+            // a user without a known timetable would look free all day
+            user.get_timetable_between(first, last)
+                .map_err(|e| format!("couldn't get the timetable of {}: {e}", user.userid))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut free_times = vec![];
+    for date in first.iter_days().take_while(|d| *d <= last) {
+        let mut busy = lessons_of
+            .iter()
+            .flatten()
+            .filter(|lsn| lsn.kezdet_idopont.date_naive() == date)
+            .filter(|lsn| !timetable::ignore_lesson(lsn))
+            .map(|lsn| (lsn.kezdet_idopont.time(), lsn.veg_idopont.time()))
+            .collect::<Vec<_>>();
+        busy.sort_unstable();
+        let free = free_between(from, until, &busy);
+        free_times.extend(free.map(|(from, to)| FreeTime { date, from, to }));
+    }
+
+    let headers = ["nap", "ettől", "eddig", "ennyi"].into_iter();
+    let disp = if args.machine { None } else { Some(display) };
+    utils::print_table(&free_times, headers, args.reverse, args.number, disp)
+}
+
+/// parse time of day as `HH:MM`
+pub fn parse_time(time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time, "%H:%M").map_err(|e| format!("invalid time: {e:?}"))
+}
+
+/// ranges between `from` and `until` not covered by any of the sorted `busy` ranges
+fn free_between(
+    from: NaiveTime,
+    until: NaiveTime,
+    busy: &[(NaiveTime, NaiveTime)],
+) -> impl Iterator<Item = (NaiveTime, NaiveTime)> {
+    let mut free = vec![];
+    let mut free_from = from;
+    for &(start, end) in busy {
+        if start > free_from {
+            free.push((free_from, start.min(until)));
+        }
+        free_from = free_from.max(end);
+    }
+    free.push((free_from, until));
+    free.into_iter().filter(|(from, to)| from < to)
+}
+
+fn display(free: &FreeTime) -> Vec<String> {
+    let date = crate::time::pretty_day(free.date);
+    let from = free.from.format("%H:%M").to_string();
+    let to = free.to.format("%H:%M").to_string();
+    let length = format!("{} perc", (free.to - free.from).num_minutes());
+    vec![date, from, to, length]
+}
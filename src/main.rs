@@ -4,7 +4,7 @@ use config::Config;
 use ekreta::Res;
 use inquire::{Confirm, MultiSelect, Text};
 use log::*;
-use std::{collections::BTreeSet, env, fs::OpenOptions, mem};
+use std::{collections::BTreeSet, env, fs::OpenOptions, mem, time::Duration};
 use time::MyDate;
 use user::User;
 
@@ -14,12 +14,20 @@ mod args;
 mod cache;
 mod config;
 mod evals;
+mod export;
+mod free;
 mod information;
 mod messages;
 mod paths;
+mod plot;
+mod report_card;
+mod school_year;
 mod schools;
+mod seen;
+mod status;
 mod time;
 mod timetable;
+mod topics;
 mod user;
 mod utils;
 
@@ -55,6 +63,10 @@ fn run(args: Args, conf: &mut Config) -> Res<()> {
         .unwrap_or(&Command::Timetable {
             day: None,
             current: false,
+            week: false,
+            changes: false,
+            watch: false,
+            refresh: 0,
         })
         .clone();
     // have a valid user
@@ -79,26 +91,123 @@ fn run(args: Args, conf: &mut Config) -> Res<()> {
             warn!("TUI is not yet written");
             Err("TUI is to be written (soon)".into())
         }
-        Command::Timetable { day, current } => timetable::handle(day, &user, current, args.machine),
+        Command::Timetable {
+            day,
+            current,
+            week,
+            changes,
+            watch,
+            refresh,
+        } => {
+            if watch {
+                timetable::watch(&user, Duration::from_secs(refresh))
+            } else {
+                timetable::handle(day, &user, conf, current, week, changes, args.machine)
+            }
+        }
+
+        Command::Next {
+            subject,
+            teacher,
+            room,
+        } => timetable::handle_next(&user, conf, subject, teacher, room, args.machine),
+
+        Command::Status { waybar } => status::handle(&user, waybar),
+        Command::ReportCard { term } => report_card::handle(&user, term, &args),
+        Command::Calendar => school_year::handle(&user, args.machine),
+
+        Command::Free {
+            day,
+            users,
+            week,
+            from,
+            until,
+        } => free::handle(conf, day, &users, week, from, until, &args),
+
+        Command::Topics { subject } => topics::handle(&user, &subject, &args),
 
         Command::Evals {
             subject: subj,
             filter,
             average,
+            by_subject,
+            plot,
+            stats,
+            target,
+            weight,
+            max,
             ghost,
-        } => evals::handle(&user, filter, subj, &ghost, average, &args),
+            new,
+            mark_seen,
+            history,
+        } => {
+            if history {
+                evals::handle_history(&user, &args)
+            } else if let Some(target) = target
+                && let Some(subj) = subj
+            {
+                let target = evals::Target {
+                    average: target,
+                    weights: &weight,
+                    max: max.into(),
+                };
+                evals::handle_target(&user, filter, &subj, &ghost, &target, &args)
+            } else if stats {
+                evals::handle_stats(&user, filter, subj, &ghost, &args)
+            } else if plot {
+                evals::handle_plot(&user, filter, subj, &ghost, &args)
+            } else if average && by_subject {
+                evals::handle_averages(&user, conf, filter, subj, &ghost, &args)
+            } else if average {
+                evals::handle_average(&user, conf, filter, subj, &ghost)
+            } else {
+                evals::handle(&user, filter, subj, &ghost, new, mark_seen, &args)
+            }
+        }
 
-        Command::Messages { notes, id } => {
+        Command::Messages {
+            notes,
+            id,
+            new,
+            mark_seen,
+        } => {
             if notes {
-                messages::handle_note_msgs(&user, id, &args)
+                messages::handle_note_msgs(&user, id, new, mark_seen, &args)
             } else {
                 messages::handle(&user, id, &args)
             }
         }
 
-        Command::Absences { count, subject } => absences::handle(&user, subject, count, &args),
+        Command::Absences {
+            count,
+            stats,
+            grouped,
+            late,
+            remaining,
+            calendar,
+            subject,
+        } => {
+            if calendar {
+                absences::handle_calendar(&user, conf, subject, &args)
+            } else if remaining {
+                absences::handle_projection(&user, subject, &args)
+            } else if late {
+                absences::handle_lateness(&user, subject, &args)
+            } else if grouped {
+                absences::handle_grouped(&user, conf, subject, &args)
+            } else if stats {
+                absences::handle_stats(&user, subject, &args)
+            } else {
+                absences::handle(&user, subject, count, &args)
+            }
+        }
 
-        Command::Tests { subject, past } => announced::handle(past, &user, subject, &args),
+        Command::Tests {
+            subject,
+            past,
+            new,
+            mark_seen,
+        } => announced::handle(past, &user, subject, new, mark_seen, &args),
 
         Command::User {
             delete,
@@ -110,6 +219,13 @@ fn run(args: Args, conf: &mut Config) -> Res<()> {
 
         Command::Schools { search } => schools::handle(search, &args),
 
+        Command::Export {
+            format,
+            from,
+            to,
+            output,
+        } => export::handle(&user, format, from, to, output),
+
         Command::NextDowntime => {
             let next_downt = user.get_userinfo()?.next_downtime();
             let probably_now = next_downt < chrono::Local::now();
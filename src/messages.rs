@@ -1,11 +1,21 @@
 //! messages from teachers and staff
 
-use crate::{paths::download_dir, time::MyDate, user::User, utils};
+use crate::{paths::download_dir, seen, time::MyDate, user::User, utils};
 use ekreta::{Endpoint, Res};
 use std::{char, fmt::Write};
 
-pub fn handle_note_msgs(user: &User, id: Option<isize>, args: &crate::Args) -> Res<()> {
+pub fn handle_note_msgs(
+    user: &User,
+    id: Option<isize>,
+    new: bool,
+    mark_seen: bool,
+    args: &crate::Args,
+) -> Res<()> {
     let notes = user.get_note_msgs((None, None))?;
+    let seen = user.seen_of(&notes)?;
+    if mark_seen {
+        user.mark_seen(&notes)?;
+    }
     if let Some(ix) = id_to_ix(id, notes.len()) {
         let Some(nm) = notes.get(ix) else {
             return Err(format!("can't find message with id: {ix}").into());
@@ -19,10 +29,18 @@ pub fn handle_note_msgs(user: &User, id: Option<isize>, args: &crate::Args) -> R
         return Ok(());
     }
 
-    let data = notes.iter().enumerate().collect::<Vec<_>>();
+    let is_new = |nm: &ekreta::NoteMsg| !seen.contains(&nm.uid);
+    // ids stay the same, even if only new ones are shown
+    let data = notes.iter().enumerate();
+    let data = data
+        .filter(|(_, nm)| !new || is_new(nm))
+        .collect::<Vec<_>>();
     let headers = ["id", "tárgya", "tőle", "ekkor"].iter();
+    let disp = |preview: &(usize, &ekreta::NoteMsg)| {
+        seen::mark_new(preview_nm(preview), is_new(preview.1))
+    };
     #[rustfmt::skip]
-    let disp = if args.machine { None } else { Some(preview_nm) };
+    let disp = if args.machine { None } else { Some(disp) };
     utils::print_table(&data, headers, args.reverse, args.number, disp)
 }
 
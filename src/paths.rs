@@ -18,6 +18,32 @@ pub fn cache_path(userid: &str, kind: &str) -> Option<PathBuf> {
     Some(cache_dir.join(format!("{kind}_cache.jsonc")))
 }
 
+/// get path for data dir, that's kept even if the cache is deleted, create if doesn't exist
+pub fn data_dir(userid: &str) -> Option<PathBuf> {
+    let data_path = dirs::data_dir()?.join(APP_NAME).join(userid);
+    if !data_path.exists() {
+        fs::create_dir_all(&data_path).ok()?;
+    }
+    Some(data_path)
+}
+
+/// get data path for `kind` of thing
+pub fn data_path(userid: &str, kind: &str) -> Option<PathBuf> {
+    let data_dir = data_dir(userid)?;
+    Some(data_dir.join(format!("{kind}.json")))
+}
+
+/// get path of the append-only history of `kind` of thing, kept in the data dir
+pub fn history_path(userid: &str, kind: &str) -> Option<PathBuf> {
+    let data_dir = data_dir(userid)?;
+    Some(data_dir.join(format!("{kind}_history.jsonl")))
+}
+
+/// get path of the dir holding ghost evals, a file for each subject, eg. `~/.config/rsfilc/ghosts/matek`
+pub fn ghost_dir() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join(APP_NAME).join("ghosts"))
+}
+
 /// get path for `Downloads/rsfilc`, and create it if doesn't exist yet
 ///
 /// # Panics
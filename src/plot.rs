@@ -0,0 +1,90 @@
+//! tiny charts for the terminal: braille line charts and sparklines
+
+use yansi::Paint;
+
+/// bits of the dots in a braille character, indexed by `[x][y]` within the 2x4 cell
+const BRAILLE_DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+/// the empty braille character
+const BRAILLE_BASE: u32 = 0x2800;
+/// bars of a sparkline, from lowest to highest
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// a point of a chart, drawn as `label` over the line
+pub struct Point {
+    /// horizontal position in dots, two of them make a character
+    pub x: usize,
+    pub y: f32,
+    pub label: String,
+}
+
+/// Draw a braille line chart of `line`, `height` characters high, between `min` and `max`.
+/// `line` holds a value for each horizontal dot, two of them make a character.
+/// `points` are drawn over the line, integer values between `min` and `max` are labelled on the left.
+pub fn line_chart(
+    line: &[Option<f32>],
+    points: &[Point],
+    height: usize,
+    min: f32,
+    max: f32,
+) -> Vec<String> {
+    let width = line.len().div_ceil(2);
+    let dots_y = height * 4 - 1;
+    let to_dot = |y: f32| {
+        let y = y.clamp(min, max);
+        ((max - y) / (max - min) * dots_y as f32).round() as usize
+    };
+
+    let mut cells = vec![vec![0; width]; height];
+    let mut prev = None;
+    for (x, y) in line.iter().enumerate() {
+        let Some(y) = y else {
+            prev = None;
+            continue;
+        };
+        let y = to_dot(*y);
+        // connect to the previous dot, so that the line is continuous
+        let (from, to) = prev.map_or((y, y), |prev: usize| (prev.min(y), prev.max(y)));
+        for dot in from..=to {
+            cells[dot / 4][x / 2] |= BRAILLE_DOTS[x % 2][dot % 4];
+        }
+        prev = Some(y);
+    }
+    let mut labels = vec![vec![None; width]; height];
+    for point in points.iter().filter(|p| p.x / 2 < width) {
+        labels[to_dot(point.y) / 4][point.x / 2] = Some(&point.label);
+    }
+
+    let axis_labels = (min.ceil() as i32..=max.floor() as i32)
+        .map(|n| (to_dot(n as f32) / 4, n))
+        .collect::<Vec<_>>();
+    let mut rows = vec![];
+    for (row, (cells, labels)) in cells.iter().zip(labels).enumerate() {
+        let mut line = if let Some((_, n)) = axis_labels.iter().find(|(r, _)| *r == row) {
+            format!("{n} ┤")
+        } else {
+            "  │".to_string()
+        };
+        for (cell, label) in cells.iter().zip(labels) {
+            if let Some(label) = label {
+                line.push_str(&label.bold().yellow().to_string());
+            } else {
+                line.push(char::from_u32(BRAILLE_BASE + cell).unwrap_or(' '));
+            }
+        }
+        rows.push(line);
+    }
+    rows
+}
+
+/// a sparkline of `values` between `min` and `max`
+pub fn sparkline(values: &[f32], min: f32, max: f32) -> String {
+    let top = SPARKS.len() - 1;
+    if max <= min {
+        return SPARKS[top / 2].to_string().repeat(values.len());
+    }
+    let spark = |v: &f32| {
+        let idx = ((v.clamp(min, max) - min) / (max - min) * top as f32).round() as usize;
+        SPARKS[idx]
+    };
+    values.iter().map(spark).collect()
+}
@@ -0,0 +1,95 @@
+//! the half-year or end-of-year report card, like the paper bizonyítvány
+
+use crate::{user::User, utils};
+use ekreta::{Evaluation, Res};
+use serde::Serialize;
+use yansi::Paint;
+
+/// part of the school year a report card is given for
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Term {
+    /// félévi
+    Half,
+    /// év végi
+    End,
+}
+
+/// a line of the report card
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportLine {
+    pub subject: String,
+    pub grade: Option<u8>,
+    /// eg. `Jeles(5)` or `Példás`, or the whole text evaluation
+    pub text: String,
+    /// whether it's magatartás or szorgalom
+    pub conduct: bool,
+}
+
+pub fn handle(user: &User, term: Option<Term>, args: &crate::Args) -> Res<()> {
+    let evals = user.get_evals((None, None))?;
+    // the latest report card by default
+    let term = term.unwrap_or(if evals.iter().any(Evaluation::evvegi) {
+        Term::End
+    } else {
+        Term::Half
+    });
+    let lines = report_card(&evals, term);
+    if !args.machine {
+        let title = match term {
+            Term::Half => "félévi értesítő",
+            Term::End => "év végi bizonyítvány",
+        };
+        println!("{}", title.bold());
+        if lines.is_empty() {
+            println!("még nincs lezárva");
+        }
+    }
+    let headers = ["tantárgy", "jegy", "szövegesen"].into_iter();
+    let disp = if args.machine { None } else { Some(display) };
+    utils::print_table(&lines, headers, false, usize::MAX, disp)
+}
+
+/// lines of the report card of `term`: magatartás and szorgalom first, then subjects in Kréta's order
+pub fn report_card(evals: &[Evaluation], term: Term) -> Vec<ReportLine> {
+    let of_term = |eval: &&Evaluation| match term {
+        Term::Half => eval.felevi(),
+        Term::End => eval.evvegi(),
+    };
+    let mut evals = evals.iter().filter(of_term).collect::<Vec<_>>();
+    evals.sort_by_key(|eval| (!is_conduct(eval), eval.tantargy.sort_index));
+    // only the latest one counts, if a grade was corrected
+    let mut lines: Vec<ReportLine> = vec![];
+    for eval in evals {
+        let line = ReportLine {
+            subject: eval.tantargy.nev.clone(),
+            grade: eval.szam_ertek,
+            text: eval.szoveges_ertek.clone(),
+            conduct: is_conduct(eval),
+        };
+        if let Some(same) = lines.iter_mut().find(|l| l.subject == line.subject) {
+            *same = line;
+        } else {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+/// Returns whether `eval` is a magatartás or szorgalom grade.
+fn is_conduct(eval: &Evaluation) -> bool {
+    let subject = eval.tantargy.nev.to_lowercase();
+    let category = eval.tantargy.kategoria.nev.to_lowercase();
+    ["magatart", "szorgalom"]
+        .iter()
+        .any(|c| subject.contains(c) || category.contains(c))
+}
+
+fn display(line: &ReportLine) -> Vec<String> {
+    let subject = if line.conduct {
+        line.subject.italic().to_string()
+    } else {
+        line.subject.clone()
+    };
+    let grade = line.grade.map(|g| g.to_string()).unwrap_or_default();
+    vec![subject, grade, line.text.clone()]
+}
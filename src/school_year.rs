@@ -0,0 +1,210 @@
+//! the school-year calendar: teaching days, breaks and term boundaries
+
+use crate::{time::pretty_day, user::User};
+use chrono::{Datelike, Local, NaiveDate, TimeDelta};
+use ekreta::{Endpoint, LDateTime, Res};
+use serde::{Deserialize, Serialize};
+use yansi::Paint;
+
+/// first day of the school year
+const YEAR_START: &str = "szorgalmi_idoszak_kezdete";
+/// last day of the school year
+const YEAR_END: &str = "szorgalmi_idoszak_vege";
+/// day-kinds on which there's no teaching, matched as part of [`DayKind::nev`]
+const NON_TEACHING: [&str; 4] = ["szunet", "tanitas_nelkuli", "munkaszuneti", "unnep"];
+
+/// an element of the school-year calendar: a day and its kind
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct SchoolDay {
+    pub uid: String,
+    pub datum: LDateTime,
+    pub naptipus: DayKind,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct DayKind {
+    pub uid: String,
+    pub nev: String,
+    pub leiras: String,
+}
+impl SchoolDay {
+    /// Returns whether there's teaching on this [`SchoolDay`].
+    pub fn teaching(&self) -> bool {
+        !NON_TEACHING
+            .iter()
+            .any(|kind| self.naptipus.nev.contains(kind))
+    }
+    /// Returns whether this [`SchoolDay`] is just a regular teaching day.
+    pub fn regular(&self) -> bool {
+        self.naptipus.nev == "tanitasi_nap" || self.naptipus.nev.contains("hetvege")
+    }
+    fn date(&self) -> NaiveDate {
+        self.datum.date_naive()
+    }
+}
+impl Endpoint for SchoolDay {
+    type Args = ();
+
+    fn path(_args: &Self::Args) -> String {
+        "/ellenorzo/V3/Sajat/Intezmenyek/TanevRendjeElemek".into()
+    }
+
+    fn when(&self) -> Option<LDateTime> {
+        Some(self.datum)
+    }
+}
+
+pub fn handle(user: &User, json: bool) -> Res<()> {
+    let cal = user.get_school_year()?;
+    let today = Local::now().date_naive();
+    let left = teaching_days_left(&cal, today);
+    let mut special = cal
+        .into_iter()
+        .filter(|sd| !sd.regular() && sd.date() >= today)
+        .collect::<Vec<_>>();
+    special.sort_unstable_by_key(SchoolDay::date);
+    if json {
+        let data = serde_json::json!({ "teaching_days_left": left, "special_days": special });
+        println!("{data}");
+        return Ok(());
+    }
+    let mut table = ascii_table::AsciiTable::default();
+    for (i, head) in ["mikor", "mi"].into_iter().enumerate() {
+        table.column(i).set_header(head);
+    }
+    let data = group_runs(&special).into_iter().map(|(from, to, kind)| {
+        let from = pretty_day(from);
+        let when = if let Some(to) = to {
+            format!("{from} - {}", pretty_day(to))
+        } else {
+            from
+        };
+        vec![when, kind]
+    });
+    table.print(data);
+    if let Some(left) = left {
+        println!("hátralévő tanítási napok: {}", left.bold());
+    }
+    Ok(())
+}
+
+/// merge `days` of the same kind into runs: (first, last if differs, kind description)
+fn group_runs(days: &[SchoolDay]) -> Vec<(NaiveDate, Option<NaiveDate>, String)> {
+    let mut runs: Vec<(NaiveDate, Option<NaiveDate>, String)> = vec![];
+    for sd in days {
+        let kind = sd.naptipus.leiras.clone();
+        // a weekend in between doesn't break the run
+        if let Some((from, to, last_kind)) = runs.last_mut()
+            && *last_kind == kind
+            && sd.date() - to.unwrap_or(*from) <= TimeDelta::days(3)
+        {
+            *to = Some(sd.date());
+        } else {
+            runs.push((sd.date(), None, kind));
+        }
+    }
+    runs
+}
+
+/// the [`SchoolDay`] `cal` has for `day`
+fn of_day(cal: &[SchoolDay], day: NaiveDate) -> Option<&SchoolDay> {
+    cal.iter().find(|sd| sd.date() == day)
+}
+/// first day of the school year
+pub fn year_start(cal: &[SchoolDay]) -> Option<NaiveDate> {
+    let start = cal.iter().find(|sd| sd.naptipus.nev == YEAR_START);
+    start
+        .or(cal.iter().min_by_key(|sd| sd.datum))
+        .map(SchoolDay::date)
+}
+/// first day of the school year `day` is in, the 1st of September if `cal` doesn't tell
+pub fn year_start_of(cal: &[SchoolDay], day: NaiveDate) -> NaiveDate {
+    let year = if day.month() >= 9 {
+        day.year()
+    } else {
+        day.year() - 1
+    };
+    let sep_first = NaiveDate::from_ymd_opt(year, 9, 1).unwrap_or(day);
+    let start = year_start(cal).filter(|start| *start <= day && start.year() == year);
+    start.unwrap_or(sep_first)
+}
+/// last day of the school year
+pub fn year_end(cal: &[SchoolDay]) -> Option<NaiveDate> {
+    let end = cal.iter().find(|sd| sd.naptipus.nev == YEAR_END);
+    end.or(cal.iter().max_by_key(|sd| sd.datum))
+        .map(SchoolDay::date)
+}
+
+/// Returns whether there's teaching on `day`.
+/// Days not in `cal` are treated as regular: weekdays within the school year are teaching days.
+pub fn is_teaching_day(cal: &[SchoolDay], day: NaiveDate) -> bool {
+    if let Some(sd) = of_day(cal, day) {
+        return sd.teaching() && !sd.naptipus.nev.contains("hetvege");
+    }
+    let in_year = year_start(cal).is_some_and(|start| start <= day)
+        && year_end(cal).is_some_and(|end| day <= end);
+    in_year && day.weekday().number_from_monday() <= 5
+}
+
+/// Returns whether there's teaching on `day`, counting every weekday if `cal` is empty.
+/// It isn't bound to the school year then, see [`year_end_of`] for its end.
+pub fn is_teaching_day_or_weekday(cal: &[SchoolDay], day: NaiveDate) -> bool {
+    if cal.is_empty() {
+        day.weekday().number_from_monday() <= 5
+    } else {
+        is_teaching_day(cal, day)
+    }
+}
+
+/// last day of the school year `day` is in, mid-June if `cal` doesn't tell
+pub fn year_end_of(cal: &[SchoolDay], day: NaiveDate) -> NaiveDate {
+    let year = if day.month() >= 9 {
+        day.year() + 1
+    } else {
+        day.year()
+    };
+    let mid_june = NaiveDate::from_ymd_opt(year, 6, 15).unwrap_or(day);
+    year_end(cal).unwrap_or(mid_june)
+}
+
+/// the first teaching day after `day`, if there's any in this school year
+pub fn next_teaching_day(cal: &[SchoolDay], day: NaiveDate) -> Option<NaiveDate> {
+    let end = year_end(cal)?;
+    day.iter_days()
+        .skip(1)
+        .take_while(|d| *d <= end)
+        .find(|d| is_teaching_day(cal, *d))
+}
+
+/// the `n`th teaching day after `day`
+/// weekdays are counted instead, where `cal` doesn't tell, eg. if it's empty
+pub fn nth_teaching_day_after(cal: &[SchoolDay], day: NaiveDate, n: u16) -> NaiveDate {
+    let mut nth = day;
+    for _ in 0..n {
+        nth = next_teaching_day(cal, nth).unwrap_or_else(|| {
+            let next = nth.iter_days().skip(1);
+            let mut weekdays = next.filter(|d| d.weekday().number_from_monday() <= 5);
+            weekdays.next().unwrap_or(nth)
+        });
+    }
+    nth
+}
+
+/// number of teaching days after `day` until the end of the school year
+pub fn teaching_days_left(cal: &[SchoolDay], day: NaiveDate) -> Option<usize> {
+    let end = year_end(cal)?;
+    let days = day.iter_days().skip(1).take_while(|d| *d <= end);
+    Some(days.filter(|d| is_teaching_day(cal, *d)).count())
+}
+
+/// If `day` is in a break (not just a weekend), returns its description and the last day of it.
+pub fn break_of(cal: &[SchoolDay], day: NaiveDate) -> Option<(String, NaiveDate)> {
+    let sd = of_day(cal, day).filter(|sd| !sd.teaching())?;
+    let until = if let Some(next) = next_teaching_day(cal, day) {
+        next - TimeDelta::days(1)
+    } else {
+        year_end(cal).unwrap_or(day).max(day)
+    };
+    Some((sd.naptipus.leiras.clone(), until))
+}
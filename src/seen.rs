@@ -0,0 +1,38 @@
+//! keeping track of which evals, announced tests and note messages the user has already seen
+
+use ekreta::{AnnouncedTest, Evaluation, NoteMsg};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use yansi::Paint;
+
+/// uids of the items already seen, by kind, see [`crate::utils::type_to_kind_name`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Seen(pub BTreeMap<String, BTreeSet<String>>);
+
+/// an item with a stable id
+pub trait Uid {
+    fn uid(&self) -> &str;
+}
+impl Uid for Evaluation {
+    fn uid(&self) -> &str {
+        &self.uid
+    }
+}
+impl Uid for AnnouncedTest {
+    fn uid(&self) -> &str {
+        &self.uid
+    }
+}
+impl Uid for NoteMsg {
+    fn uid(&self) -> &str {
+        &self.uid
+    }
+}
+
+/// Mark the first cell of `row` if `new`.
+pub fn mark_new(mut row: Vec<String>, new: bool) -> Vec<String> {
+    if new && let Some(first) = row.first_mut() {
+        *first = format!("{} {first}", "új".green().bold());
+    }
+    row
+}
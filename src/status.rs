@@ -0,0 +1,146 @@
+//! a single line about the current or next lesson for status bars, eg. waybar, i3blocks, polybar, tmux
+
+use crate::{timetable, user::User};
+use chrono::Local;
+use ekreta::{AnnouncedTest, LDateTime, Lesson, Res};
+use serde::Serialize;
+
+/// what a status bar needs, shaped like waybar's custom module expects it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Status {
+    pub text: String,
+    pub tooltip: String,
+    /// one of: `current`, `next`, `cancelled`, `test`, `free`
+    pub class: String,
+}
+
+/// print status, only using cached data, so that it's cheap to run every few seconds
+pub fn handle(user: &User, waybar: bool) -> Res<()> {
+    let lessons = user.cached_timetable();
+    let tests = user.cached_tests();
+    let status = status(&lessons, &tests, Local::now());
+    if waybar {
+        println!("{}", serde_json::to_string(&status)?);
+    } else {
+        println!("{}", status.text);
+    }
+    Ok(())
+}
+
+/// status at `now`, cancelled lessons are kept, so that they can be shown
+fn status(lessons: &[Lesson], tests: &[AnnouncedTest], now: LDateTime) -> Status {
+    let today = now.date_naive();
+    let of_today = |lsn: &&Lesson| lsn.kezdet_idopont.date_naive() == today;
+    let todays = lessons.iter().filter(of_today).cloned().collect::<Vec<_>>();
+    let real_lesson = |lsn: &&Lesson| lsn.cancelled() || !timetable::ignore_lesson(lsn);
+    let remaining = todays
+        .iter()
+        .filter(real_lesson)
+        .filter(|lsn| lsn.veg_idopont > now)
+        .collect::<Vec<_>>();
+    let tooltip = remaining
+        .iter()
+        .map(|lsn| {
+            let start = lsn.kezdet_idopont.format("%H:%M").to_string();
+            let cancelled = if lsn.cancelled() { "elmarad:" } else { "" };
+            let room = timetable::disp_room(lsn);
+            let parts = [start.as_str(), cancelled, &lsn.nev, &room];
+            join_present(&parts, " ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let test_of = |lsn: &Lesson| {
+        tests.iter().any(|t| {
+            t.datum.date_naive() == lsn.kezdet_idopont.date_naive()
+                && t.orarendi_ora_oraszama == lsn.oraszam
+        })
+    };
+    let started = |lsn: &&&Lesson| lsn.kezdet_idopont <= now;
+    let current = remaining
+        .iter()
+        .filter(started)
+        .find(|lsn| !lsn.cancelled());
+    let (lsn, till, class) = if let Some(cnt_lsn) = current {
+        (*cnt_lsn, cnt_lsn.veg_idopont, "current")
+    } else if let Some(nxt) = remaining.iter().find(|lsn| lsn.kezdet_idopont > now) {
+        (*nxt, nxt.kezdet_idopont, "next")
+    } else {
+        let class = "free".to_string();
+        return Status {
+            text: String::new(),
+            tooltip,
+            class,
+        };
+    };
+    let class = if lsn.cancelled() {
+        "cancelled"
+    } else if test_of(lsn) {
+        "test"
+    } else {
+        class
+    };
+    let name = if lsn.cancelled() {
+        format!("elmarad: {}", lsn.nev)
+    } else {
+        lsn.nev.clone()
+    };
+    let name = join_present(&[&name, &timetable::disp_room(lsn)], " ");
+    let text = format!("{name} · {}m", timetable::mins_till(till));
+    Status {
+        text,
+        tooltip,
+        class: class.to_string(),
+    }
+}
+
+/// `parts` that aren't empty, joined by `sep`
+fn join_present(parts: &[&str], sep: &str) -> String {
+    let present = parts.iter().filter(|part| !part.is_empty());
+    present.copied().collect::<Vec<_>>().join(sep)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone};
+
+    fn lesson(nev: &str, kezdet: LDateTime, cancelled: bool) -> Lesson {
+        // `Rektip` isn't exported by ekreta
+        let allapot = r#"{"Uid": "", "Nev": "Elmaradt", "Leiras": ""}"#;
+        let allapot = cancelled.then(|| serde_json::from_str(allapot).unwrap());
+        Lesson {
+            nev: nev.into(),
+            kezdet_idopont: kezdet,
+            veg_idopont: kezdet + TimeDelta::minutes(45),
+            allapot,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cancelled_next_lesson_shown() {
+        let now = Local.with_ymd_and_hms(2026, 10, 19, 8, 50, 0).unwrap();
+        let lessons = [
+            lesson("matek", now - TimeDelta::minutes(50), false),
+            lesson("fizika", now + TimeDelta::minutes(10), true),
+            lesson("kémia", now + TimeDelta::minutes(65), false),
+        ];
+        let status = status(&lessons, &[], now);
+        assert_eq!(status.class, "cancelled");
+        assert!(status.text.starts_with("elmarad: fizika"));
+        assert!(status.tooltip.contains("elmarad: fizika"));
+    }
+
+    #[test]
+    fn current_lesson_preferred() {
+        let now = Local.with_ymd_and_hms(2026, 10, 19, 8, 10, 0).unwrap();
+        let lessons = [
+            lesson("matek", now - TimeDelta::minutes(10), false),
+            lesson("fizika", now + TimeDelta::minutes(50), true),
+        ];
+        let status = status(&lessons, &[], now);
+        assert_eq!(status.class, "current");
+        assert!(status.text.starts_with("matek"));
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::{Datelike, Local, Timelike};
+use chrono::{Datelike, Local, NaiveDate, NaiveTime, Timelike};
 use ekreta::LDateTime;
 
 /// `DateTime` methods needed for `RsFilc`
@@ -80,3 +80,16 @@ impl MyDate for LDateTime {
         }
     }
 }
+
+/// the beginning of `day` in the local timezone
+pub fn local_midnight(day: NaiveDate) -> Option<LDateTime> {
+    day.and_time(NaiveTime::MIN)
+        .and_local_timezone(Local)
+        .earliest()
+}
+
+/// practical format of a `day`, see [`MyDate::pretty`]
+pub fn pretty_day(day: NaiveDate) -> String {
+    let midnight = local_midnight(day);
+    midnight.map(|dt| dt.pretty()).unwrap_or(day.to_string())
+}
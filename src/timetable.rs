@@ -1,19 +1,90 @@
 //! lessons the student has
 
-use crate::{time::MyDate, user::User};
-use chrono::{Datelike, Local, NaiveDate, TimeDelta};
+use crate::{config::Config, school_year, time::MyDate, user::User};
+use chrono::{Datelike, Local, NaiveDate, TimeDelta, Weekday};
 use ekreta::{AnnouncedTest, LDateTime, Lesson, Res};
 use log::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeSet,
+    fmt::Write as _,
+    io::{self, Write},
+    thread,
+    time::{Duration, Instant},
+};
 use yansi::Paint;
 
-pub fn handle(day: Option<NaiveDate>, user: &User, current: bool, json: bool) -> Res<()> {
-    let day = day.unwrap_or(default_day(user));
+pub fn handle(
+    day: Option<NaiveDate>,
+    user: &User,
+    conf: &Config,
+    current: bool,
+    week: bool,
+    changes: bool,
+    json: bool,
+) -> Res<()> {
+    let today = Local::now().date_naive();
+    if changes {
+        // changes are noticed while fetching
+        if let Err(e) = user.get_timetable(day.unwrap_or(today), true) {
+            warn!("couldn't refresh lessons, showing the changes noticed so far: {e:?}");
+        }
+        let mut changes = user.get_lesson_changes();
+        changes.retain(|change| change.lesson.veg_idopont >= Local::now());
+        changes.sort_by_key(|change| (change.lesson.kezdet_idopont, change.noticed));
+        if changes.is_empty() && !json {
+            println!("nem változott semmi a közelgő óráidban");
+            return Ok(());
+        }
+        #[rustfmt::skip]
+        let headers = ["ekkor", ".", "tantárgy", "változás", "észlelve"].into_iter();
+        let disp = if json { None } else { Some(disp_change) };
+        return crate::utils::print_table(&changes, headers, false, usize::MAX, disp);
+    }
+    let day = if let Some(day) = day {
+        Some(day)
+    } else {
+        default_day(user, conf.lookahead_days)?
+    };
+    // the week is worth showing even if there's no lesson in it
+    let Some(day) = day.or(week.then_some(today)) else {
+        if json {
+            println!("null");
+        } else {
+            let days = conf.lookahead_days;
+            println!("nincs közelgő órád a következő {days} napban, juhé!");
+        }
+        return Ok(());
+    };
     debug!("showing day: {day}");
     let lessons_of_week = user.get_timetable(day, true)?;
+    if week {
+        let monday = day.week(Weekday::Mon).first_day();
+        let in_week = |lsn: &&Lesson| {
+            let lsn_day = lsn.kezdet_idopont.date_naive();
+            monday <= lsn_day && lsn_day < monday + TimeDelta::days(5)
+        };
+        let lessons = lessons_of_week.iter().filter(in_week).cloned();
+        let lessons = lessons.collect::<Vec<_>>();
+        if json {
+            let json = serde_json::to_string(&lessons)?;
+            println!("{json}");
+        } else {
+            user.print_week(monday, &lessons);
+        }
+        return Ok(());
+    }
     let lessons = user.get_timetable(day, false)?;
     if lessons.is_empty() {
         if json {
             println!("null");
+        } else if let Some((kind, until)) = user
+            .get_school_year()
+            .ok()
+            .and_then(|cal| school_year::break_of(&cal, day))
+        {
+            let until = crate::time::pretty_day(until);
+            println!("{day} ({}): {kind}, {until}-ig, juhé!", day.weekday());
         } else {
             println!("{day} ({}) nincs rögzített órád, juhé!", day.weekday());
         }
@@ -47,8 +118,148 @@ pub fn handle(day: Option<NaiveDate>, user: &User, current: bool, json: bool) ->
     Ok(())
 }
 
+/// find the next lesson of `subj`, with `teacher` in `room`, whichever are given
+pub fn handle_next(
+    user: &User,
+    conf: &Config,
+    subj: Option<String>,
+    teacher: Option<String>,
+    room: Option<String>,
+    json: bool,
+) -> Res<()> {
+    let today = Local::now().date_naive();
+    let contains = |text: Option<&String>, search: &str| {
+        text.is_some_and(|text| text.to_lowercase().contains(&search.to_lowercase()))
+    };
+    let horizon = today + TimeDelta::days(conf.lookahead_days.into());
+    let mut found = None;
+    let mut monday = today.week(Weekday::Mon).first_day();
+    while monday <= horizon {
+        let (from, to) = (
+            monday.max(today),
+            (monday + TimeDelta::days(6)).min(horizon),
+        );
+        monday += TimeDelta::weeks(1);
+        let mut lessons = user.get_timetable_between(from, to)?;
+        lessons.retain(|lsn| lsn.forecoming() && !ignore_lesson(lsn));
+        if let Some(subject) = &subj {
+            filter_by_subject(&mut lessons, subject);
+        }
+        if let Some(teacher) = &teacher {
+            lessons.retain(|lsn| {
+                let actual_teacher = lsn
+                    .helyettes_tanar_neve
+                    .as_ref()
+                    .or(lsn.tanar_neve.as_ref());
+                contains(actual_teacher, teacher)
+            });
+        }
+        if let Some(room) = &room {
+            lessons.retain(|lsn| contains(lsn.terem_neve.as_ref(), room));
+        }
+        if let Some(lsn) = lessons.into_iter().next() {
+            found = Some(lsn);
+            break;
+        }
+    }
+    let Some(lsn) = found else {
+        if json {
+            println!("null");
+        } else {
+            let days = conf.lookahead_days;
+            println!("nincs ilyen órád a következő {days} napban");
+        }
+        return Ok(());
+    };
+    let mins_to_start = mins_till(lsn.kezdet_idopont);
+    if json {
+        let data = serde_json::json!({ "mins_till": mins_to_start, "lesson": lsn });
+        println!("{data}");
+    } else {
+        let when = lsn.kezdet_idopont.pretty();
+        let till = lsn.veg_idopont.format("%H:%M");
+        let room = disp_room(&lsn);
+        let teacher = disp_teacher(&lsn);
+        println!(
+            "{}: {when} - {till}, {mins_to_start} perc múlva",
+            lsn.nev.bold()
+        );
+        println!("terem: {room}, {teacher}");
+    }
+    Ok(())
+}
+
+/// how often to redraw the screen while watching
+const REDRAW_EVERY: Duration = Duration::from_secs(10);
+/// width of the progress bar of the current lesson
+const PROGRESS_WIDTH: usize = 30;
+
+/// Redraw the current and next lesson until interrupted, refetching lessons every `refresh`.
+pub fn watch(user: &User, refresh: Duration) -> Res<()> {
+    let mut lessons = vec![];
+    let mut fetched: Option<(Instant, NaiveDate)> = None;
+    loop {
+        let today = Local::now().date_naive();
+        if fetched.is_none_or(|(at, day)| at.elapsed() >= refresh || day != today) {
+            debug!("refreshing lessons while watching");
+            lessons = user.get_timetable_between(today, today + TimeDelta::weeks(1))?;
+            fetched = Some((Instant::now(), today));
+        }
+        // clear the screen and move to the top
+        print!("\x1b[2J\x1b[H{}", watch_screen(&lessons));
+        io::stdout().flush()?;
+        thread::sleep(REDRAW_EVERY);
+    }
+}
+
+/// compact screen of the current lesson with a progress bar and the next lesson
+fn watch_screen(lessons: &[Lesson]) -> String {
+    let mut screen = String::new();
+    let now = Local::now();
+    let after = |time: LDateTime| {
+        let later = move |lsn: &&Lesson| lsn.kezdet_idopont >= time && !ignore_lesson(lsn);
+        lessons.iter().find(later)
+    };
+    let cnt_lessons = current_lessons(lessons);
+    if let Some(cnt_lsn) = cnt_lessons.first() {
+        let total = (cnt_lsn.veg_idopont - cnt_lsn.kezdet_idopont)
+            .num_seconds()
+            .max(1);
+        let done = (now - cnt_lsn.kezdet_idopont).num_seconds().clamp(0, total);
+        let filled = PROGRESS_WIDTH * done as usize / total as usize;
+        let bar = format!(
+            "{}{}",
+            "█".repeat(filled),
+            "░".repeat(PROGRESS_WIDTH - filled)
+        );
+        let room = disp_room(cnt_lsn);
+        _ = writeln!(&mut screen, "{} · {room}", cnt_lsn.nev.bold());
+        let left = mins_till(cnt_lsn.veg_idopont);
+        _ = writeln!(&mut screen, "{} {left} perc van hátra", bar.cyan());
+        if let Some(nxt) = after(cnt_lsn.veg_idopont) {
+            let break_len = (nxt.kezdet_idopont - cnt_lsn.veg_idopont).num_minutes();
+            let room = disp_room(nxt);
+            _ = writeln!(
+                &mut screen,
+                "szünet: {break_len} perc, utána: {} · {room}",
+                nxt.nev
+            );
+        }
+    } else if let Some(nxt) = after(now) {
+        let start = nxt.kezdet_idopont.format("%H:%M");
+        let mins_to_start = mins_till(nxt.kezdet_idopont);
+        let room = disp_room(nxt);
+        _ = writeln!(&mut screen, "{} · {room}", nxt.nev.bold());
+        let till = format!("{mins_to_start} perc múlva, {start}");
+        _ = writeln!(&mut screen, "{}", till.yellow());
+    } else {
+        _ = writeln!(&mut screen, "nincs közelgő órád, juhé!");
+    }
+    screen
+}
+
 /// minutes `till` now
-fn mins_till(till: LDateTime) -> i64 {
+pub fn mins_till(till: LDateTime) -> i64 {
     (till - Local::now()).num_minutes()
 }
 /// Parse the day got as `argument`.
@@ -96,37 +307,70 @@ pub fn next_lesson(lessons: &[Lesson]) -> Option<&Lesson> {
         .iter()
         .find(|lsn| lsn.forecoming() && !ignore_lesson(lsn))
 }
+/// Filter `lessons` by `subject`
+pub fn filter_by_subject(lessons: &mut Vec<Lesson>, subj: &str) {
+    log::info!("filtering lessons by subject: {subj}");
+    let subj = subj.to_lowercase();
+    lessons.retain(|lsn| {
+        lsn.nev.to_lowercase().contains(&subj)
+            || lsn
+                .tantargy
+                .as_ref()
+                .is_some_and(|t| t.nev.to_lowercase().contains(&subj))
+    });
+}
+
 /// whether it's fake or cancelled
-fn ignore_lesson(lsn: &Lesson) -> bool {
+pub fn ignore_lesson(lsn: &Lesson) -> bool {
     lsn.kamu_smafu() || lsn.cancelled() || lsn.nev == EMPTY_NAME
 }
 
-/// you may want to check `lsn` validity: `lsn.kamu_smafu()`
-pub fn disp(lsn: &Lesson, past_lessons: &[Lesson], test: Option<&AnnouncedTest>) -> Vec<String> {
-    let topic = lsn
-        .tema
-        .as_ref()
-        .map(|t| [": ", &t.italic().to_string()].concat())
-        .unwrap_or_default();
-    let name = format!("{}{topic}", lsn.nev);
-    let name = if lsn.cancelled() {
+/// mark `name` of `lsn` red, if it's cancelled
+fn mark_cancelled(lsn: &Lesson, name: String) -> String {
+    if lsn.cancelled() {
         let past_morpheme = if lsn.forecoming() { "" } else { "t" };
         format!("elmarad{past_morpheme}: {name}").red().to_string()
     } else {
         name
-    };
-    let room = lsn
-        .clone()
-        .terem_neve
+    }
+}
+/// room of `lsn` without the needless "terem"
+pub fn disp_room(lsn: &Lesson) -> String {
+    lsn.terem_neve
+        .as_deref()
         .unwrap_or_default()
         .replace("terem", "")
         .trim()
-        .to_string();
-    let teacher = if let Some(sub_teacher) = &lsn.helyettes_tanar_neve {
+        .to_string()
+}
+/// teacher of `lsn`, substitute teacher is preferred and highlighted
+fn disp_teacher(lsn: &Lesson) -> String {
+    if let Some(sub_teacher) = &lsn.helyettes_tanar_neve {
         format!("helyettes: {}", sub_teacher.underline())
     } else {
         lsn.tanar_neve.clone().unwrap_or_default()
+    }
+}
+/// kind and topic of an announced `test`
+fn disp_test(test: &AnnouncedTest) -> String {
+    let topic = if let Some(topic) = test.temaja.as_ref() {
+        format!(": {}", topic.italic())
+    } else {
+        String::new()
     };
+    format!("{}{topic}", test.modja.leiras.bold())
+}
+
+/// you may want to check `lsn` validity: `lsn.kamu_smafu()`
+pub fn disp(lsn: &Lesson, past_lessons: &[Lesson], test: Option<&AnnouncedTest>) -> Vec<String> {
+    let topic = lsn
+        .tema
+        .as_ref()
+        .map(|t| [": ", &t.italic().to_string()].concat())
+        .unwrap_or_default();
+    let name = mark_cancelled(lsn, format!("{}{topic}", lsn.nev));
+    let room = disp_room(lsn);
+    let teacher = disp_teacher(lsn);
     let mins_to_start = mins_till(lsn.kezdet_idopont);
     let from = if next_lesson(past_lessons).is_some_and(|nxt| nxt == lsn) && mins_to_start < 120 {
         format!("{mins_to_start} perc").yellow().to_string()
@@ -147,18 +391,36 @@ pub fn disp(lsn: &Lesson, past_lessons: &[Lesson], test: Option<&AnnouncedTest>)
         row.push("hiányoztál".to_string());
     }
     if let Some(existing_test) = test {
-        let topic = if let Some(topic) = existing_test.temaja.as_ref() {
-            format!(": {}", topic.italic())
-        } else {
-            String::new()
-        };
-        let test = format!("{}{}", existing_test.modja.leiras.bold(), topic);
-        row.push(test);
+        row.push(disp_test(existing_test));
     }
 
     row
 }
 
+/// a cell of the week grid: name (and test) in the first, room and teacher in the second line
+fn disp_cell(lessons: &[&Lesson], tests: &[AnnouncedTest]) -> [String; 2] {
+    let mut names = vec![];
+    let mut details = vec![];
+    for lsn in lessons {
+        let same_lsn = |t: &&AnnouncedTest| {
+            t.datum.date_naive() == lsn.kezdet_idopont.date_naive()
+                && t.orarendi_ora_oraszama == lsn.oraszam
+        };
+        let name = mark_cancelled(lsn, lsn.nev.clone());
+        let name = if let Some(test) = tests.iter().find(same_lsn) {
+            format!("{name}, {}", test.modja.leiras.bold())
+        } else {
+            name
+        };
+        names.push(name);
+        let room = disp_room(lsn);
+        let teacher = disp_teacher(lsn);
+        let detail = [room, teacher].into_iter().filter(|s| !s.is_empty());
+        details.push(detail.collect::<Vec<_>>().join(", "));
+    }
+    [names.join(" / "), details.join(" / ")]
+}
+
 impl User {
     /// print all lessons of a day
     pub fn print_day(&self, mut lessons: Vec<Lesson>, lessons_of_week: &[Lesson]) {
@@ -215,6 +477,181 @@ impl User {
     }
 }
 
+impl User {
+    /// print all lessons of the week starting on `monday` as a grid: lesson numbers as rows, days as columns
+    pub fn print_week(&self, monday: NaiveDate, lessons: &[Lesson]) {
+        if lessons.is_empty() {
+            println!("{monday} hetében nincs rögzített órád, juhé!");
+            return;
+        }
+        let friday = monday + TimeDelta::days(4);
+        let tests = self
+            .get_tests((Some(monday), Some(friday)))
+            .unwrap_or_default();
+        let days = (0..5)
+            .map(|n| monday + TimeDelta::days(n))
+            .collect::<Vec<_>>();
+        let of_day = |day: NaiveDate| {
+            let same_day = move |lsn: &&Lesson| lsn.kezdet_idopont.date_naive() == day;
+            lessons.iter().filter(same_day)
+        };
+
+        let mut table = ascii_table::AsciiTable::default();
+        table.column(0).set_header(".");
+        for (i, day) in days.iter().enumerate() {
+            let header = if let Some(title) = of_day(*day).find(|lsn| lsn.kamu_smafu()) {
+                title.nev.clone()
+            } else {
+                crate::time::local_midnight(*day)
+                    .map(|dt| format!("{}, {}", dt.hun_day_of_week(), dt.pretty()))
+                    .unwrap_or(day.to_string())
+            };
+            table.column(i + 1).set_header(header);
+        }
+
+        let real_lesson = |lsn: &&Lesson| !lsn.kamu_smafu();
+        let nths = lessons.iter().filter(real_lesson).map(Lesson::idx);
+        let nths = nths.collect::<BTreeSet<_>>();
+        let mut data = vec![];
+        for nth in nths {
+            let mut name_row = vec![nth.to_string()];
+            let mut detail_row = vec![String::new()];
+            for day in &days {
+                let same_n = |lsn: &&Lesson| lsn.idx() == nth;
+                let lsns = of_day(*day).filter(real_lesson).filter(same_n);
+                let lsns = lsns.collect::<Vec<_>>();
+                let [name, detail] = if lsns.is_empty() {
+                    let around = |earlier: bool| {
+                        of_day(*day)
+                            .filter(real_lesson)
+                            .any(|lsn| (lsn.idx() < nth) == earlier)
+                    };
+                    // a gap between two lessons
+                    if around(true) && around(false) {
+                        [EMPTY_NAME.dim().to_string(), String::new()]
+                    } else {
+                        [String::new(), String::new()]
+                    }
+                } else {
+                    disp_cell(&lsns, &tests)
+                };
+                name_row.push(name);
+                detail_row.push(detail);
+            }
+            data.push(name_row);
+            data.push(detail_row);
+        }
+        table.print(data);
+    }
+}
+
+/// a change of a [`Lesson`], noticed between its cached and freshly fetched version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LessonChange {
+    /// when the change was noticed
+    pub noticed: LDateTime,
+    /// the lesson, after the change
+    pub lesson: Lesson,
+    pub kind: ChangeKind,
+}
+/// what has changed about a [`Lesson`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    /// got a new substitute teacher
+    Substitute(String),
+    /// got cancelled
+    Cancelled,
+    /// moved to another room
+    Room(Option<String>, Option<String>),
+    /// start or end time changed: previous (start, end)
+    Time(LDateTime, LDateTime),
+}
+
+/// Collect the changes between the `cached` and `fetched` version of each lesson slot.
+/// A slot is the same `uid`, or the same day and lesson number.
+pub fn diff_lessons(cached: &[Lesson], fetched: &[Lesson]) -> Vec<LessonChange> {
+    let noticed = Local::now();
+    let mut changes = vec![];
+    for fl in fetched.iter().filter(|fl| !fl.kamu_smafu()) {
+        let same_slot = |cl: &&Lesson| {
+            !cl.kamu_smafu()
+                && (cl.uid == fl.uid
+                    || cl.oraszam.is_some()
+                        && cl.oraszam == fl.oraszam
+                        && cl.kezdet_idopont.date_naive() == fl.kezdet_idopont.date_naive())
+        };
+        let Some(cl) = cached.iter().find(same_slot) else {
+            continue;
+        };
+        let mut change = |kind| {
+            let lesson = fl.clone();
+            debug!("lesson changed: {kind:?}, {lesson:?}");
+            changes.push(LessonChange {
+                noticed,
+                lesson,
+                kind,
+            });
+        };
+        if let Some(sub_teacher) = &fl.helyettes_tanar_neve
+            && cl.helyettes_tanar_neve.as_ref() != Some(sub_teacher)
+        {
+            change(ChangeKind::Substitute(sub_teacher.clone()));
+        }
+        if fl.cancelled() && !cl.cancelled() {
+            change(ChangeKind::Cancelled);
+        }
+        if fl.terem_neve != cl.terem_neve {
+            let (from, to) = (cl.terem_neve.clone(), fl.terem_neve.clone());
+            change(ChangeKind::Room(from, to));
+        }
+        if !fl.same_time(cl) {
+            change(ChangeKind::Time(cl.kezdet_idopont, cl.veg_idopont));
+        }
+    }
+    changes
+}
+
+fn disp_change(change: &LessonChange) -> Vec<String> {
+    let lsn = &change.lesson;
+    let when = lsn.kezdet_idopont.pretty();
+    let num = lsn.idx().to_string();
+    let name = lsn.nev.clone();
+    let hm = |t: &LDateTime| t.format("%H:%M").to_string();
+    let what = match &change.kind {
+        ChangeKind::Substitute(teacher) => format!("helyettes: {}", teacher.underline()),
+        ChangeKind::Cancelled => "elmarad".red().to_string(),
+        ChangeKind::Room(from, to) => {
+            let from = from.clone().unwrap_or_default();
+            format!("terem: {from} -> {}", to.clone().unwrap_or_default().bold())
+        }
+        ChangeKind::Time(from, to) => {
+            let prev = format!("{} - {}", hm(from), hm(to));
+            let now = format!("{} - {}", hm(&lsn.kezdet_idopont), hm(&lsn.veg_idopont));
+            format!("időpont: {prev} -> {}", now.bold())
+        }
+    };
+    let noticed = change.noticed.pretty();
+    vec![when, num, name, what, noticed]
+}
+
+/// a week is refetched for this long after it has ended, as topics might still get filled in
+const TOPIC_GRACE: TimeDelta = TimeDelta::weeks(3);
+
+/// a week of lessons, starting on `monday`, that has been fetched at `fetched`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FetchedWeek {
+    pub monday: NaiveDate,
+    pub fetched: LDateTime,
+}
+impl FetchedWeek {
+    /// Returns whether the week was fetched [`TOPIC_GRACE`] after it had ended, so cached lessons of it are final.
+    /// Topics are often filled in days or weeks later.
+    pub fn final_after(&self, monday: NaiveDate) -> bool {
+        let last_day = monday + TimeDelta::days(6);
+        self.monday == monday && self.fetched.date_naive() > last_day + TOPIC_GRACE
+    }
+}
+
 /// name given to an empty lesson
 const EMPTY_NAME: &str = "lukas";
 
@@ -237,25 +674,37 @@ fn nth_lesson_when(n: u8, ref_lessons: &[Lesson]) -> (Option<LDateTime>, Option<
     ref_lessons.iter().find(same_n).map(extract_irval).unzip()
 }
 
-pub fn default_day(user: &User) -> NaiveDate {
+/// The day worth showing: today, if its lessons aren't over yet, otherwise the next day with lessons.
+/// Looks ahead `horizon` days at most, a whole week at once, skipping weeks without teaching days.
+/// # Errors
+/// lessons of a week can be neither fetched nor loaded from cache
+pub fn default_day(user: &User, horizon: u16) -> Res<Option<NaiveDate>> {
     let now = Local::now();
     let today = now.date_naive();
-    let end_of_today = if let Ok(mut lessons) = user.get_timetable(today, false) {
-        lessons.retain(|l| !ignore_lesson(l));
-        lessons.last().map(|l| l.veg_idopont)
-    } else {
-        return today;
-    };
+    let horizon = today + TimeDelta::days(horizon.into());
+    // an empty calendar tells nothing, just like a missing one
+    let cal = user.get_school_year().ok().filter(|cal| !cal.is_empty());
 
-    let mut skip_days = TimeDelta::days(0);
-    if end_of_today.is_none_or(|eot| eot < now) {
-        skip_days = TimeDelta::days(1); // skipping today, as it's already done
-        while let Ok(lsns) = user.get_timetable(today + skip_days, false) {
-            if next_lesson(&lsns).is_some() {
-                break;
+    let mut monday = today.week(Weekday::Mon).first_day();
+    while monday <= horizon {
+        let (from, to) = (
+            monday.max(today),
+            (monday + TimeDelta::days(6)).min(horizon),
+        );
+        let teaching_in_week = |cal: &[_]| {
+            let mut days = from.iter_days().take_while(|d| *d <= to);
+            days.any(|d| school_year::is_teaching_day(cal, d))
+        };
+        if cal.as_deref().is_none_or(teaching_in_week) {
+            let lessons = user.get_timetable_between(from, to)?;
+            let upcoming = |lsn: &&Lesson| !ignore_lesson(lsn) && lsn.veg_idopont > now;
+            if let Some(lsn) = lessons.iter().find(upcoming) {
+                return Ok(Some(lsn.kezdet_idopont.date_naive()));
             }
-            skip_days += TimeDelta::days(1);
+        } else {
+            debug!("no teaching days between {from} and {to}, not fetching");
         }
+        monday += TimeDelta::weeks(1);
     }
-    today + skip_days
+    Ok(None)
 }
@@ -0,0 +1,36 @@
+//! topics of the lessons of a subject throughout the school year
+
+use crate::{time::MyDate, timetable, user::User, utils};
+use chrono::Local;
+use ekreta::{Lesson, Res};
+use yansi::Paint;
+
+pub fn handle(user: &User, subj: &str, args: &crate::Args) -> Res<()> {
+    let today = Local::now().date_naive();
+    let mut lessons = user.get_timetable_between(user.school_year_start(today), today)?;
+    lessons.retain(|lsn| !lsn.kamu_smafu());
+    timetable::filter_by_subject(&mut lessons, subj);
+
+    let headers = ["dátum", ".", "tantárgy", "téma", "extra"].into_iter();
+    let disp = if args.machine { None } else { Some(display) };
+    utils::print_table(&lessons, headers, args.reverse, args.number, disp)
+}
+
+fn display(lsn: &Lesson) -> Vec<String> {
+    let date = lsn.datum.pretty();
+    let num = lsn.idx().to_string();
+    let name = lsn.nev.clone();
+    let topic = lsn.tema.clone().unwrap_or_default();
+    let mut extra = vec![];
+    if lsn.cancelled() {
+        extra.push("elmaradt".red().to_string());
+    }
+    if let Some(sub_teacher) = &lsn.helyettes_tanar_neve {
+        extra.push(format!("helyettes: {}", sub_teacher.underline()));
+    }
+    if lsn.absent() {
+        extra.push("hiányoztál".to_string());
+    }
+
+    vec![date, num, name, topic, extra.join(", ")]
+}
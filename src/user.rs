@@ -1,5 +1,12 @@
-use crate::{config::Config, *};
-use chrono::{Datelike, Local, NaiveDate, TimeDelta};
+use crate::{
+    config::Config,
+    evals::{ClassAverage, EvalChange},
+    school_year::{self, SchoolDay},
+    seen::{Seen, Uid},
+    timetable::{FetchedWeek, LessonChange},
+    *,
+};
+use chrono::{Datelike, Local, NaiveDate, TimeDelta, Weekday};
 use ekreta::{
     Absence, Account, AnnouncedTest as Ancd, Evaluation as Eval, HeaderMap, LDateTime, Lesson,
     MsgItem, MsgOview, OptIrval, Token, consts, header,
@@ -156,6 +163,44 @@ impl User {
         let content = serde_json::to_string(content)?;
         cache::store(&self.userid, &kind, &content)
     }
+    /// helper fn, stores `content` of `kind` as data, that's kept even if `NO_CACHE` is set or the cache is deleted
+    fn store_data<S: Serialize>(&self, content: &S) -> Res<()> {
+        let kind = utils::type_to_kind_name::<S>()?;
+        let content = serde_json::to_string(content)?;
+        cache::store_data(&self.userid, &kind, &content)
+    }
+    /// helper fn, loads data of `kind`, `None` if it's not stored yet
+    /// # Errors
+    /// it's stored, but couldn't be read or deserialized
+    fn load_data<D: for<'a> Deserialize<'a>>(&self) -> Res<Option<D>> {
+        let kind = utils::type_to_kind_name::<D>()?;
+        let Some(content) = cache::load_data(&self.userid, &kind)? else {
+            return Ok(None);
+        };
+        let deserd = serde_json::from_str(&content)
+            .inspect_err(|e| error!("{e:?} - couldn't deserialize {kind}: {content}"))?;
+        Ok(Some(deserd))
+    }
+    /// helper fn, appends `items` to the history of their kind
+    fn append_history<S: Serialize>(&self, items: &[S]) -> Res<()> {
+        let kind = utils::type_to_kind_name::<S>()?;
+        let lines = items.iter().map(serde_json::to_string);
+        let lines = lines.collect::<Result<Vec<_>, _>>()?;
+        cache::append_history(&self.userid, &kind, &lines)
+    }
+    /// helper fn, loads the history of `kind`, skipping entries that can't be deserialized
+    fn load_history<D: for<'a> Deserialize<'a>>(&self) -> Vec<D> {
+        let Ok(kind) = utils::type_to_kind_name::<D>() else {
+            return vec![];
+        };
+        let lines = cache::load_history(&self.userid, &kind);
+        let deser = |line: &String| {
+            serde_json::from_str(line)
+                .inspect_err(|e| error!("{e:?} - couldn't deserialize {kind}: {line}"))
+                .ok()
+        };
+        lines.iter().filter_map(deser).collect()
+    }
     /// helper fn, loads cache of `kind` from `self.0.userid` cache-dir
     fn load_cache<D: for<'a> Deserialize<'a>>(&self) -> Option<(LDateTime, D)> {
         let kind = utils::type_to_kind_name::<D>().ok()?;
@@ -163,15 +208,23 @@ impl User {
             log::info!("manually triggered 'no cache' error");
             return None;
         }
-
-        let (cache_t, content) = cache::load(&self.userid, &kind)?;
-        let deserd = serde_json::from_str(&content)
+        self.read_cache()
             .inspect_err(|e| {
-                error!("{e:?} - couldn't deserialize {kind}: {content}");
                 eprintln!("error: {e:?}, check logs with `cat $(rsfilc --cache-dir)/rsfilc.log`");
             })
-            .ok()?;
-        Some((cache_t, deserd))
+            .ok()?
+    }
+    /// helper fn, loads cache of `kind` even if `NO_CACHE` is set
+    /// # Errors
+    /// the cache exists, but couldn't be deserialized
+    fn read_cache<D: for<'a> Deserialize<'a>>(&self) -> Res<Option<(LDateTime, D)>> {
+        let kind = utils::type_to_kind_name::<D>()?;
+        let Some((cache_t, content)) = cache::load(&self.userid, &kind) else {
+            return Ok(None);
+        };
+        let deserd = serde_json::from_str(&content)
+            .inspect_err(|e| error!("{e:?} - couldn't deserialize {kind}: {content}"))?;
+        Ok(Some((cache_t, deserd)))
     }
     fn fetch_vec<E>(&self, query: E::Args) -> Res<Vec<E>>
     where
@@ -218,21 +271,115 @@ impl User {
         Ok(token)
     }
     pub fn get_userinfo(&self) -> Res<ekreta::UserInfo> {
-        if let Some((_, cached_info)) = self.load_cache::<ekreta::UserInfo>() {
-            if cached_info.next_downtime() + TimeDelta::hours(4) > Local::now() {
-                return Ok(cached_info);
-            }
+        if let Some((_, cached_info)) = self.load_cache::<ekreta::UserInfo>()
+            && cached_info.next_downtime() + TimeDelta::hours(4) > Local::now()
+        {
+            return Ok(cached_info);
         }
         let fetched_info = self.account.fetch_info(&self.headers()?)?;
         self.store_cache(&fetched_info)?;
         Ok(fetched_info)
     }
 
-    gen_get_for! { get_evals, Eval, false,
-        (|evals: &mut Vec<Eval>| {
+    /// the school-year calendar, refreshed weekly
+    pub fn get_school_year(&self) -> Res<Vec<SchoolDay>> {
+        let cached = self.load_cache::<Vec<SchoolDay>>();
+        if let Some((cache_t, cached_cal)) = cached.as_ref()
+            && Local::now() - *cache_t < TimeDelta::weeks(1)
+        {
+            return Ok(cached_cal.clone());
+        }
+        match self.fetch_vec(()) {
+            Ok(mut fetched_cal) => {
+                fetched_cal.sort_unstable_by_key(|sd: &SchoolDay| sd.datum);
+                self.store_cache(&fetched_cal)?;
+                Ok(fetched_cal)
+            }
+            Err(e) => {
+                error!("only loading cached calendar, couldn't reach E-Kréta server: {e:?}");
+                let (_, cached_cal) = cached.ok_or("nothing cached")?;
+                Ok(cached_cal)
+            }
+        }
+    }
+
+    /// first day of the school year `day` is in, see [`school_year::year_start_of`]
+    pub fn school_year_start(&self, day: NaiveDate) -> NaiveDate {
+        let cal = self.get_school_year().unwrap_or_default();
+        school_year::year_start_of(&cal, day)
+    }
+
+    /// class averages of each subject, falls back to cache
+    /// can't use [`gen_get_for`], as it needs the education task of the class instead of an interval
+    pub fn get_class_averages(&self) -> Res<Vec<ClassAverage>> {
+        let fetched = self.fetch_vec::<ekreta::Class>(()).and_then(|classes| {
+            let class = classes.iter().find(|c| c.is_aktiv).or(classes.first());
+            let task_uid = class
+                .ok_or("no class")?
+                .oktatas_nevelesi_feladat
+                .uid
+                .clone();
+            self.fetch_vec::<ClassAverage>(task_uid)
+        });
+        match fetched {
+            Ok(class_avgs) => {
+                self.store_cache(&class_avgs)?;
+                Ok(class_avgs)
+            }
+            Err(e) => {
+                error!("only loading cached class averages, couldn't reach E-Kréta server: {e:?}");
+                let (_, cached) = self.load_cache().ok_or("nothing cached")?;
+                Ok(cached)
+            }
+        }
+    }
+
+    /// get all items between `from` and `to`
+    /// without an interval, the whole school year is refetched on every call and compared to the cached evals,
+    /// so that modified and deleted ones are noted, see [`evals::diff_evals`]
+    /// cached evals of earlier school years are kept, as they aren't fetched
+    /// # Errors
+    /// net
+    pub fn get_evals(&self, interval: OptIrval) -> Res<Vec<Eval>> {
+        let sort = |evals: &mut Vec<Eval>| {
             evals.sort_unstable_by_key(|e| e.keszites_datuma);
             evals.dedup_by_key(|e| e.uid.clone());
-        })
+        };
+        if interval != (None, None) {
+            let mut evals = self.load_n_fetch::<Eval>(interval, true)?;
+            sort(&mut evals);
+            return Ok(evals);
+        }
+        let (_, cached) = self.load_cache::<Vec<Eval>>().unzip();
+        let mut evals = match self.fetch_vec::<Eval>(interval) {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                error!("only loading cached evaluation, couldn't reach E-Kréta server: {e:?}");
+                eprintln!("only loading cached evaluation, couldn't reach E-Kréta server: {e:?}");
+                return Ok(cached.ok_or("nothing cached")?);
+            }
+        };
+        let year_start = self.school_year_start(Local::now().date_naive());
+        if let Some(cached) = cached {
+            let changes = evals::diff_evals(&cached, &evals, year_start);
+            if !changes.is_empty() {
+                info!("noticed {} changes of evals", changes.len());
+                self.append_history(&changes)?;
+            }
+            // an empty fetch is much more likely a glitch, so everything cached is kept then
+            let keep = |e: &Eval| evals.is_empty() || e.keszites_datuma.date_naive() < year_start;
+            let fetched = |e: &Eval| evals.iter().any(|f| f.uid == e.uid);
+            let kept = cached.into_iter().filter(|e| keep(e) && !fetched(e));
+            let kept = kept.collect::<Vec<_>>();
+            evals.extend(kept);
+        }
+        sort(&mut evals);
+        self.store_cache(&evals)?;
+        Ok(evals)
+    }
+    /// changes of evals noticed while fetching them, oldest first
+    pub fn get_eval_history(&self) -> Vec<EvalChange> {
+        self.load_history()
     }
 
     pub fn get_timetable(&self, day: NaiveDate, whole_week: bool) -> Res<Vec<Lesson>> {
@@ -251,6 +398,13 @@ impl User {
                 debug!("warm lesson cache hit (< 8s), using instead of fetching");
                 return Ok(lessons.iter().filter(|&x| is_cached(x)).cloned().collect());
             }
+            let (_, weeks) = self.load_cache::<Vec<FetchedWeek>>().unzip();
+            let just_fetched =
+                |week: &FetchedWeek| week.monday == from && fresh_cache(week.fetched);
+            if whole_week && weeks.unwrap_or_default().iter().any(just_fetched) {
+                debug!("warm week cache hit (< 8s), using instead of fetching");
+                return Ok(lessons.clone());
+            }
         }
         let remain_relevant = |lessons: &mut Vec<Lesson>| {
             if !whole_week {
@@ -260,6 +414,10 @@ impl User {
         match self.fetch_vec((from, to)) {
             Ok(mut fetched_items) => {
                 let mut lessons = cached_tt.unwrap_or_default();
+                let changes = timetable::diff_lessons(&lessons, &fetched_items);
+                if !changes.is_empty() {
+                    self.store_lesson_changes(changes)?;
+                }
                 // delete cached if same but fresh was fetched
                 lessons.retain(|cl| {
                     !fetched_items.iter().any(|fl: &Lesson| {
@@ -271,6 +429,9 @@ impl User {
                 lessons.append(&mut fetched_items);
                 lessons.sort_unstable_by_key(|l| l.kezdet_idopont);
                 self.store_cache(&lessons)?;
+                if whole_week {
+                    self.store_fetched_week(from)?;
+                }
                 remain_relevant(&mut lessons);
                 Ok(lessons)
             }
@@ -289,11 +450,139 @@ impl User {
         }
     }
 
-    gen_get_for! { get_tests, Ancd, false,
-        (|tests: &mut Vec<Ancd>| {
+    /// lessons in cache, without touching the network
+    pub fn cached_timetable(&self) -> Vec<Lesson> {
+        let (_, lessons) = self.load_cache::<Vec<Lesson>>().unzip();
+        lessons.unwrap_or_default()
+    }
+    /// announced tests in cache, without touching the network
+    pub fn cached_tests(&self) -> Vec<Ancd> {
+        let (_, tests) = self.load_cache::<Vec<Ancd>>().unzip();
+        tests.unwrap_or_default()
+    }
+
+    /// uids of `items` already seen, all of them are considered seen if nothing's tracked yet
+    /// if what's seen couldn't be loaded, all of them are considered seen, but nothing's stored
+    pub fn seen_of<T: Uid>(&self, items: &[T]) -> Res<BTreeSet<String>> {
+        let kind = utils::type_to_kind_name::<T>()?;
+        let all_uids = || items.iter().map(|item| item.uid().to_string()).collect();
+        let mut seen = match self.load_data::<Seen>() {
+            Ok(seen) => seen.unwrap_or_default(),
+            Err(e) => {
+                warn!("couldn't load what's seen, treating all of {kind} as seen: {e:?}");
+                return Ok(all_uids());
+            }
+        };
+        if let Some(uids) = seen.0.get(&kind) {
+            return Ok(uids.clone());
+        }
+        info!(
+            "nothing seen of {kind} yet, marking all {} of them",
+            items.len()
+        );
+        let uids: BTreeSet<_> = all_uids();
+        seen.0.insert(kind, uids.clone());
+        self.store_data(&seen)?;
+        Ok(uids)
+    }
+    /// Mark all `items` as seen.
+    /// # Errors
+    /// what's already seen couldn't be loaded, so it's not overwritten
+    pub fn mark_seen<T: Uid>(&self, items: &[T]) -> Res<()> {
+        let kind = utils::type_to_kind_name::<T>()?;
+        let mut seen = self.load_data::<Seen>()?.unwrap_or_default();
+        let uids = items.iter().map(|item| item.uid().to_string());
+        seen.0.entry(kind).or_default().extend(uids);
+        self.store_data(&seen)
+    }
+
+    /// changes of lessons noticed while fetching the timetable, see [`timetable::diff_lessons`]
+    /// these are kept even if `NO_CACHE` is set
+    pub fn get_lesson_changes(&self) -> Vec<LessonChange> {
+        let changes = self.read_cache::<Vec<LessonChange>>().ok().flatten();
+        changes.map(|(_, changes)| changes).unwrap_or_default()
+    }
+    /// add `changes` to the already noticed ones, forgetting about the ones older than a week
+    /// the noticed ones are left as they are if they couldn't be loaded
+    fn store_lesson_changes(&self, mut changes: Vec<LessonChange>) -> Res<()> {
+        let mut all_changes = match self.read_cache::<Vec<LessonChange>>() {
+            Ok(noticed) => noticed.map(|(_, noticed)| noticed).unwrap_or_default(),
+            Err(e) => {
+                warn!("couldn't load noticed lesson changes, not storing new ones: {e:?}");
+                return Ok(());
+            }
+        };
+        all_changes.append(&mut changes);
+        let week_ago = Local::now() - TimeDelta::weeks(1);
+        all_changes.retain(|change| change.lesson.veg_idopont > week_ago);
+        self.store_cache(&all_changes)
+    }
+
+    /// remember that the week starting on `monday` has just been fetched
+    fn store_fetched_week(&self, monday: NaiveDate) -> Res<()> {
+        let (_, weeks) = self.load_cache::<Vec<FetchedWeek>>().unzip();
+        let mut weeks = weeks.unwrap_or_default();
+        weeks.retain(|week| week.monday != monday);
+        let fetched = Local::now();
+        weeks.push(FetchedWeek { monday, fetched });
+        weeks.sort_unstable_by_key(|week| week.monday);
+        self.store_cache(&weeks)
+    }
+
+    /// get all lessons between `from` and `to` (inclusive), fetching them week by week
+    /// # NOTE
+    /// - weeks that were fetched well after they had ended are loaded from cache, see [`FetchedWeek::final_after`]
+    /// # Errors
+    /// a week can be neither fetched nor loaded from cache
+    pub fn get_timetable_between(&self, from: NaiveDate, to: NaiveDate) -> Res<Vec<Lesson>> {
+        let (_, fetched_weeks) = self.load_cache::<Vec<FetchedWeek>>().unzip();
+        let fetched_weeks = fetched_weeks.unwrap_or_default();
+        let (_, cached_tt) = self.load_cache::<Vec<Lesson>>().unzip();
+        let cached_tt = cached_tt.unwrap_or_default();
+
+        let mut lessons = Vec::new();
+        let mut monday = from.week(Weekday::Mon).first_day();
+        while monday <= to {
+            let sunday = monday + TimeDelta::days(6);
+            let in_week =
+                |lsn: &Lesson| (monday..=sunday).contains(&lsn.kezdet_idopont.date_naive());
+            if fetched_weeks.iter().any(|week| week.final_after(monday)) {
+                debug!("week of {monday} is over and was fetched afterwards, using cache");
+                lessons.extend(cached_tt.iter().filter(|lsn| in_week(lsn)).cloned());
+            } else {
+                let of_week = self.get_timetable(monday, true).inspect_err(|e| {
+                    warn!("couldn't get lessons of the week of {monday}: {e:?}");
+                })?;
+                lessons.extend(of_week.into_iter().filter(in_week));
+            }
+            monday += TimeDelta::weeks(1);
+        }
+        lessons.retain(|lsn| (from..=to).contains(&lsn.kezdet_idopont.date_naive()));
+        Ok(lessons)
+    }
+
+    /// get all items between `from` and `to`
+    /// fetched ones are merged into the cache even with an interval, so that [`Self::cached_tests`] stays fresh
+    /// # Errors
+    /// net
+    pub fn get_tests(&self, interval: OptIrval) -> Res<Vec<Ancd>> {
+        let sort = |tests: &mut Vec<Ancd>| {
             tests.sort_unstable_by_key(|a| (a.datum, a.uid.clone()));
             tests.dedup_by_key(|a| a.uid.clone());
-        })
+        };
+        let mut tests = self.load_n_fetch::<Ancd>(interval, true)?;
+        sort(&mut tests);
+        let mut all = if interval.0.is_none() {
+            vec![]
+        } else {
+            let (_, cached) = self.load_cache::<Vec<Ancd>>().unzip();
+            cached.unwrap_or_default()
+        };
+        all.retain(|cached| !tests.iter().any(|test| test.uid == cached.uid));
+        all.extend(tests.iter().cloned());
+        sort(&mut all);
+        self.store_cache(&all)?;
+        Ok(tests)
     }
 
     gen_get_for! { get_absences, Absence, true,